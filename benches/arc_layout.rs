@@ -0,0 +1,142 @@
+//! `ArcMake` (data 를 `ManuallyDrop<T>` 로 보관) 와 이전 `Option<T>` layout,
+//! `std::sync::Arc` 의 deref/drop 비용 비교.
+//!
+//! `cargo bench --bench arc_layout`
+
+use atomic_and_locks::arc::ArcMake;
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+mod option_layout {
+    //! `UnsafeCell<Option<T>>` 를 쓰던 p.154 의 layout (비교용)
+
+    use std::cell::UnsafeCell;
+    use std::ops::Deref;
+    use std::ptr::NonNull;
+    use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+    use std::sync::atomic::{fence, AtomicUsize};
+
+    struct ArcData<T> {
+        data_ref_count: AtomicUsize,
+        alloc_ref_count: AtomicUsize,
+        data: UnsafeCell<Option<T>>,
+    }
+
+    pub struct OptionArc<T> {
+        ptr: NonNull<ArcData<T>>,
+    }
+
+    impl<T> OptionArc<T> {
+        pub fn new(data: T) -> Self {
+            OptionArc {
+                ptr: NonNull::from(Box::leak(Box::new(ArcData {
+                    data_ref_count: AtomicUsize::new(1),
+                    alloc_ref_count: AtomicUsize::new(1),
+                    data: UnsafeCell::new(Some(data)),
+                }))),
+            }
+        }
+
+        fn data(&self) -> &ArcData<T> {
+            unsafe { self.ptr.as_ref() }
+        }
+    }
+
+    impl<T> Deref for OptionArc<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            unsafe { (*self.data().data.get()).as_ref().unwrap() }
+        }
+    }
+
+    impl<T> Clone for OptionArc<T> {
+        fn clone(&self) -> Self {
+            self.data().alloc_ref_count.fetch_add(1, Relaxed);
+            self.data().data_ref_count.fetch_add(1, Relaxed);
+            OptionArc { ptr: self.ptr }
+        }
+    }
+
+    impl<T> Drop for OptionArc<T> {
+        fn drop(&mut self) {
+            if self.data().data_ref_count.fetch_sub(1, Release) == 1 {
+                fence(Acquire);
+                unsafe { *self.data().data.get() = None };
+            }
+            if self.data().alloc_ref_count.fetch_sub(1, Release) == 1 {
+                fence(Acquire);
+                drop(unsafe { Box::from_raw(self.ptr.as_ptr()) });
+            }
+        }
+    }
+}
+
+use option_layout::OptionArc;
+
+const DEREFS: usize = 50_000_000;
+const DROPS: usize = 1_000_000;
+
+fn time(f: impl FnOnce()) -> Duration {
+    let start = Instant::now();
+    f();
+    start.elapsed()
+}
+
+fn deref<P: std::ops::Deref<Target = u64>>(p: &P) -> Duration {
+    time(|| {
+        let mut sum = 0u64;
+        for _ in 0..DEREFS {
+            sum = sum.wrapping_add(**black_box(p));
+        }
+        black_box(sum);
+    })
+}
+
+fn drop_all<P>(new: impl Fn(Vec<u64>) -> P) -> Duration {
+    let all: Vec<P> = (0..DROPS).map(|i| new(vec![i as u64])).collect();
+    time(|| drop(black_box(all)))
+}
+
+fn clone_drop<P: Clone>(p: &P) -> Duration {
+    time(|| {
+        for _ in 0..DEREFS / 10 {
+            drop(black_box(p.clone()));
+        }
+    })
+}
+
+fn main() {
+    println!(
+        "{:<12} {:>12} {:>12} {:>12}",
+        "", "deref", "drop", "clone+drop"
+    );
+
+    let a = ArcMake::new(1u64);
+    println!(
+        "{:<12} {:>12?} {:>12?} {:>12?}",
+        "ArcMake",
+        deref(&a),
+        drop_all(ArcMake::new),
+        clone_drop(&a),
+    );
+
+    let o = OptionArc::new(1u64);
+    println!(
+        "{:<12} {:>12?} {:>12?} {:>12?}",
+        "Option<T>",
+        deref(&o),
+        drop_all(OptionArc::new),
+        clone_drop(&o),
+    );
+
+    let s = Arc::new(1u64);
+    println!(
+        "{:<12} {:>12?} {:>12?} {:>12?}",
+        "std Arc",
+        deref(&s),
+        drop_all(Arc::new),
+        clone_drop(&s),
+    );
+}
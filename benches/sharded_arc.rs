@@ -0,0 +1,44 @@
+//! 여러 스레드가 같은 handle 을 clone/drop 할 때 p.145 `ArcMake` (counter 하나)
+//! 와 `ShardedArc` (stripe 별 counter) 비교.
+//!
+//! `cargo bench --bench sharded_arc`
+
+use atomic_and_locks::arc::basic::ArcMake;
+use atomic_and_locks::arc::ShardedArc;
+use std::hint::black_box;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CLONES: usize = 1_000_000;
+
+/// Time for `threads` threads to each clone+drop `CLONES` times.
+fn clone_drop<P: Clone + Send + Sync>(p: &P, threads: usize) -> Duration {
+    let start = Instant::now();
+    thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| {
+                // worker 가 자기 handle 을 하나 들고 있는 흔한 경우
+                let local = p.clone();
+                for _ in 0..CLONES {
+                    drop(black_box(local.clone()));
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn main() {
+    let a = ArcMake::new(1u64);
+    let s = ShardedArc::new(1u64);
+
+    println!("{:<8} {:>14} {:>14}", "threads", "p.145", "ShardedArc");
+    for threads in [1, 2, 4, 8, 16, 32] {
+        println!(
+            "{:<8} {:>14?} {:>14?}",
+            threads,
+            clone_drop(&a, threads),
+            clone_drop(&s, threads),
+        );
+    }
+}
@@ -1,15 +1,11 @@
-use std::{
-    sync::atomic::{AtomicUsize, AtomicU64, AtomicBool},
-    thread,
-    time::Duration
-};
-use std::cmp::max;
-use std::sync::atomic::{fence, AtomicPtr};
-use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release, SeqCst};
-use std::time::Instant;
+use std::sync::atomic::fence;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use std::{thread, time::Duration};
 
 static mut DATA: [u64; 10] = [0; 10];
 
+#[allow(clippy::declare_interior_mutable_const)]
 const ATOMIC_FALSE: AtomicBool = AtomicBool::new(false);
 static READY: [AtomicBool; 10] = [ATOMIC_FALSE; 10];
 
@@ -37,4 +33,4 @@ fn some_calculation(n: usize) -> u64 {
     thread::sleep(Duration::from_millis(500));
     println!("calculation: {}", n);
     n as u64
-}
\ No newline at end of file
+}
@@ -0,0 +1,28 @@
+use atomic_and_locks::channel::mpsc::{self, Channel};
+use std::thread;
+
+fn main() {
+    let channel = Channel::new();
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for i in 0..10 {
+                channel.send(i);
+            }
+        });
+        let sum: i32 = (0..10).map(|_| channel.receive()).sum();
+        assert_eq!(sum, 45);
+    });
+
+    // sender 가 모두 drop 되면 recv 가 Err 를 돌려주므로 개수를 몰라도 됨
+    let (tx, rx) = mpsc::bounded(4);
+    thread::scope(|s| {
+        s.spawn(move || {
+            for i in 0..10 {
+                tx.send(i).unwrap();
+            }
+        });
+        let sum: i32 = std::iter::from_fn(|| rx.recv().ok()).sum();
+        assert_eq!(sum, 45);
+    });
+}
@@ -0,0 +1,20 @@
+use atomic_and_locks::channel::oneshot::unchecked::Channel;
+use std::thread;
+
+fn main() {
+    let channel = Channel::new();
+    let t = thread::current();
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            // 안전함: send 는 한번만 호출
+            unsafe { channel.send("hoho") };
+            t.unpark();
+        });
+        while !channel.is_ready() {
+            thread::park();
+        }
+        // 안전함: is_ready() 확인 후 한번만 호출
+        assert_eq!(unsafe { channel.receive() }, "hoho");
+    });
+}
@@ -0,0 +1,18 @@
+use atomic_and_locks::channel::oneshot::atomic_state::Channel;
+use std::thread;
+
+fn main() {
+    let channel = Channel::new();
+    let t = thread::current();
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            channel.send("hoho");
+            t.unpark();
+        });
+        while !channel.is_ready() {
+            thread::park();
+        }
+        assert_eq!(channel.receive(), "hoho");
+    });
+}
@@ -0,0 +1,20 @@
+use atomic_and_locks::channel::oneshot;
+use std::thread;
+
+fn main() {
+    thread::scope(|s| {
+        let (sender, receiver) = oneshot::channel();
+        let t = thread::current();
+
+        s.spawn(move || {
+            sender.send("hi");
+            t.unpark();
+        });
+
+        while !receiver.is_ready() {
+            thread::park();
+        }
+
+        assert_eq!(receiver.receive(), Ok("hi"));
+    })
+}
@@ -0,0 +1,20 @@
+use atomic_and_locks::channel::oneshot::borrowed::Channel;
+use std::thread;
+
+fn main() {
+    let mut channel = Channel::new();
+
+    thread::scope(|s| {
+        let (sender, receiver) = channel.split();
+        let t = thread::current();
+
+        s.spawn(move || {
+            sender.send("hi");
+            t.unpark();
+        });
+        while !receiver.is_ready() {
+            thread::park();
+        }
+        assert_eq!(receiver.receive(), "hi");
+    })
+}
@@ -0,0 +1,15 @@
+use atomic_and_locks::channel::oneshot::blocking::Channel;
+use std::thread;
+
+fn main() {
+    let mut channel = Channel::new();
+
+    thread::scope(|s| {
+        let (sender, receiver) = channel.split();
+
+        s.spawn(move || {
+            sender.send("hi");
+        });
+        assert_eq!(receiver.receive(), Ok("hi"));
+    })
+}
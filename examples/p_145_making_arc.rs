@@ -0,0 +1,11 @@
+use atomic_and_locks::arc::basic::ArcMake;
+use std::thread;
+
+fn main() {
+    let a = ArcMake::new(vec![1, 2, 3]);
+    let b = a.clone();
+
+    let t = thread::spawn(move || b.iter().sum::<i32>());
+    assert_eq!(t.join().unwrap(), 6);
+    assert_eq!(a.len(), 3);
+}
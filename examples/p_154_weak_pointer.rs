@@ -0,0 +1,27 @@
+use atomic_and_locks::arc::{ArcMake, WeakMake};
+use std::thread;
+
+fn main() {
+    let mut a = ArcMake::new(String::from("hello"));
+    ArcMake::get_mut(&mut a).unwrap().push_str(" world");
+
+    let w: WeakMake<String> = ArcMake::downgrade(&a);
+    // WeakMake 가 있으면 get_mut 불가능
+    assert!(ArcMake::get_mut(&mut a).is_none());
+
+    let t = thread::spawn(move || w.upgrade().map(|s| s.len()));
+    assert_eq!(t.join().unwrap(), Some(11));
+
+    let w = ArcMake::downgrade(&a);
+    drop(a);
+    assert!(w.upgrade().is_none());
+
+    // cargo run --example p_154_weak_pointer --features leak-tracking
+    #[cfg(feature = "leak-tracking")]
+    for leak in atomic_and_locks::arc::arc_make::live_allocations() {
+        println!(
+            "{} (strong {}, weak {})",
+            leak.type_name, leak.strong_count, leak.weak_count
+        );
+    }
+}
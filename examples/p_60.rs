@@ -1,5 +1,5 @@
-use std::{sync::atomic::AtomicUsize, thread, time::Duration};
 use std::sync::atomic::Ordering::Relaxed;
+use std::{sync::atomic::AtomicUsize, thread, time::Duration};
 
 fn main() {
     let num_done = AtomicUsize::new(0);
@@ -24,6 +24,6 @@ fn main() {
 }
 
 fn process_item(i: usize) {
-    println!("do!!!");
+    println!("do!!! {i}");
     thread::sleep(Duration::from_millis(100));
-}
\ No newline at end of file
+}
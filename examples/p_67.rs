@@ -1,11 +1,10 @@
+use std::sync::atomic::Ordering::Relaxed;
+use std::time::Instant;
 use std::{
-    sync::atomic::{AtomicUsize, AtomicU64},
+    sync::atomic::{AtomicU64, AtomicUsize},
     thread,
-    time::Duration
+    time::Duration,
 };
-use std::cmp::max;
-use std::sync::atomic::Ordering::Relaxed;
-use std::time::Instant;
 
 fn main() {
     let num_done = &AtomicUsize::new(0);
@@ -51,6 +50,6 @@ fn main() {
 }
 
 fn process_item(i: usize) {
-    println!("do!!!");
+    println!("do!!! {i}");
     thread::sleep(Duration::from_millis(150));
-}
\ No newline at end of file
+}
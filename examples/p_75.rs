@@ -1,3 +1,5 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering::Relaxed;
 
@@ -14,4 +16,15 @@ fn get_key() -> u64 {
     } else {
         key
     }
-}
\ No newline at end of file
+}
+
+fn generate_random_key() -> u64 {
+    // 0 은 "아직 없음" 으로 사용
+    RandomState::new().hash_one(0u8).max(1)
+}
+
+fn main() {
+    let key = get_key();
+    assert_eq!(get_key(), key);
+    println!("key: {key:#x}");
+}
@@ -1,11 +1,9 @@
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
 use std::{
-    sync::atomic::{AtomicUsize, AtomicU64, AtomicBool},
+    sync::atomic::{AtomicBool, AtomicU64},
     thread,
-    time::Duration
+    time::Duration,
 };
-use std::cmp::max;
-use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release};
-use std::time::Instant;
 
 static DATA: AtomicU64 = AtomicU64::new(0);
 static READY: AtomicBool = AtomicBool::new(false);
@@ -20,4 +18,4 @@ fn main() {
         println!("waiting.....");
     }
     println!("{}", DATA.load(Relaxed));
-}
\ No newline at end of file
+}
@@ -0,0 +1,17 @@
+use atomic_and_locks::memory_ordering::TryLock;
+use std::thread;
+
+static DATA: TryLock<String> = TryLock::new(String::new());
+
+fn f() {
+    DATA.try_with(|data| data.push('!'));
+}
+
+fn main() {
+    thread::scope(|s| {
+        for _ in 0..100 {
+            s.spawn(f);
+        }
+    });
+    println!("{:?}", DATA.try_with(|data| data.len()));
+}
@@ -0,0 +1,40 @@
+use atomic_and_locks::arc::ArcMake;
+use atomic_and_locks::memory_ordering::LazyBox;
+use std::thread;
+
+#[derive(Debug)]
+struct Data {
+    values: Vec<u64>,
+}
+
+fn generate_data() -> Data {
+    println!("generate_data on {:?}", thread::current().id());
+    Data {
+        values: (1..=10).collect(),
+    }
+}
+
+fn get_data() -> &'static Data {
+    static DATA: LazyBox<Data> = LazyBox::new();
+    DATA.get_or_init(generate_data)
+}
+
+// 소유권이 필요한 곳에 넘길 때: immortal 이라 clone 이 counter 를 쓰지 않음
+fn get_shared_data() -> ArcMake<Data> {
+    static DATA: LazyBox<ArcMake<Data>> = LazyBox::new();
+    DATA.get_or_init(|| ArcMake::new_immortal(generate_data()))
+        .clone()
+}
+
+fn main() {
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                let sum: u64 = get_data().values.iter().sum();
+                assert_eq!(sum, 55);
+                assert_eq!(get_shared_data().values.len(), 10);
+            });
+        }
+    });
+    println!("{:?}", get_data());
+}
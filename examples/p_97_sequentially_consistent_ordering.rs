@@ -1,8 +1,6 @@
-use std::{
-    sync::atomic::{AtomicBool},
-    thread,
-};
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::SeqCst;
+use std::thread;
 
 static A: AtomicBool = AtomicBool::new(false);
 static B: AtomicBool = AtomicBool::new(false);
@@ -13,17 +11,26 @@ fn main() {
     let a = thread::spawn(|| {
         A.store(true, SeqCst);
         if !B.load(SeqCst) {
-            unsafe { S.push('!') };
+            push_bang();
         }
     });
 
     let b = thread::spawn(|| {
-        B.store (true, SeqCst);
+        B.store(true, SeqCst);
         if !A.load(SeqCst) {
-            unsafe { S.push('!') };
+            push_bang();
         }
     });
 
     a.join().unwrap();
     b.join().unwrap();
+
+    // 두 스레드 중 최대 하나만 push
+    let s = &raw const S;
+    println!("{:?}", unsafe { &*s });
+}
+
+fn push_bang() {
+    let s = &raw mut S;
+    unsafe { (*s).push('!') };
 }
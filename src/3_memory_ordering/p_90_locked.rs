@@ -1,29 +0,0 @@
-use std::{
-    sync::atomic::{AtomicUsize, AtomicU64, AtomicBool},
-    thread,
-    time::Duration
-};
-use std::cmp::max;
-use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release};
-use std::time::Instant;
-
-static mut DATA: String = String::new();
-static LOCKED: AtomicBool = AtomicBool::new(false);
-
-fn f() {
-    if LOCKED
-        .compare_exchange(false, true, Acquire, Relaxed)
-        .is_ok()
-    {
-        unsafe { DATA.push('!') };
-        LOCKED.store(false, Release);
-    }
-}
-
-fn main() {
-    thread::scope(|s| {
-        for _ in 0..100 {
-            s.spawn(f);
-        }
-    })
-}
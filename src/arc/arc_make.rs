@@ -0,0 +1,1076 @@
+//! `ArcMake` with weak pointer support (p.154).
+
+use crate::arc::allocator::{Allocator, Global};
+use std::alloc::{self, Layout};
+use std::borrow::Borrow;
+use std::cell::UnsafeCell;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem::{self, ManuallyDrop};
+use std::ops::Deref;
+use std::ptr::{self, NonNull};
+use std::sync::atomic::fence;
+
+#[cfg(feature = "leak-tracking")]
+mod leaks;
+#[cfg(feature = "leak-tracking")]
+pub use leaks::{live_allocations, LiveAllocation};
+#[cfg(feature = "serde")]
+mod serde;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+
+#[cfg(not(feature = "compact-counts"))]
+type Count = std::sync::atomic::AtomicUsize;
+#[cfg(not(feature = "compact-counts"))]
+type CountInt = usize;
+
+// feature "compact-counts": header 가 8 bytes 로 줄어드는 대신 한도가 낮아짐
+#[cfg(feature = "compact-counts")]
+type Count = std::sync::atomic::AtomicU32;
+#[cfg(feature = "compact-counts")]
+type CountInt = u32;
+
+// 한도를 넘긴 뒤 다른 스레드가 더 올려도 wrap 되지 않도록 절반만 사용
+const MAX_COUNT: CountInt = CountInt::MAX / 2;
+
+/// Largest reference count; going past it aborts `clone` and fails `try_clone`.
+#[allow(clippy::unnecessary_cast)] // compact-counts 에서는 u32
+pub const MAX_REFCOUNT: usize = MAX_COUNT as usize;
+
+#[allow(clippy::unnecessary_cast)]
+fn to_usize(n: CountInt) -> usize {
+    n as usize
+}
+
+/// A reference count would have exceeded [`MAX_REFCOUNT`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefCountOverflow;
+
+impl fmt::Display for RefCountOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("reference count overflow")
+    }
+}
+
+impl std::error::Error for RefCountOverflow {}
+
+// 두 counter 가 모두 이 값이면 해제되지 않는 allocation: clone/drop 이 counter 를 건드리지 않음
+// MAX_COUNT 를 넘으면 abort 하므로 실제 count 가 여기까지 올라올 수 없음
+const IMMORTAL: CountInt = CountInt::MAX;
+
+fn is_immortal(count: &Count) -> bool {
+    count.load(Relaxed) == IMMORTAL
+}
+
+// data 가 없어진 뒤의 data_ref_count 에서 layout 이 여기 들어 있다는 표시 (ArcData::set_dropped)
+// IMMORTAL 처럼 MAX_COUNT 위라 실제 count 와 겹치지 않음
+const DROPPED: CountInt = MAX_COUNT + 1;
+
+/// `data_ref_count` as a number of `ArcMake`s: 0 once the data is gone.
+fn strong_of(n: CountInt) -> CountInt {
+    if n >= DROPPED && n != IMMORTAL {
+        0
+    } else {
+        n
+    }
+}
+
+/// Adds one to `count` unless it is already at [`MAX_REFCOUNT`].
+fn try_increment(count: &Count) -> Result<(), RefCountOverflow> {
+    if is_immortal(count) {
+        return Ok(());
+    }
+    count
+        .fetch_update(Relaxed, Relaxed, |n| (n < MAX_COUNT).then_some(n + 1))
+        .map(drop)
+        .map_err(|_| RefCountOverflow)
+}
+
+/// Adds one to `count`, aborting once it goes past [`MAX_REFCOUNT`].
+///
+/// Unwinding is not an option: the counter is already incremented and other
+/// threads keep cloning, so the count could wrap and free live data.
+fn increment(count: &Count) {
+    if is_immortal(count) {
+        return;
+    }
+    if count.fetch_add(1, Relaxed) > MAX_COUNT {
+        std::process::abort();
+    }
+}
+
+// data 가 마지막 필드여야 unsized T 가능
+#[repr(C)]
+struct ArcData<T: ?Sized> {
+    // ArcMake 개수
+    data_ref_count: Count,
+    // ArcMake + WeakMake 개수 (ArcMake 도 각자 WeakMake 를 하나씩 가짐)
+    alloc_ref_count: Count,
+    // 마지막 ArcMake 가 drop 되면 그 자리에서 drop
+    data: UnsafeCell<ManuallyDrop<T>>,
+}
+
+/// Layout of an `ArcData` whose data has the given layout.
+fn arc_data_layout(value: Layout) -> Layout {
+    Layout::new::<ArcData<()>>()
+        .extend(value)
+        .unwrap()
+        .0
+        .pad_to_align()
+}
+
+/// Offset of `data` inside an `ArcData` for data with the given alignment.
+fn data_offset(align: usize) -> usize {
+    let header = mem::offset_of!(ArcData<()>, data);
+    (header + align - 1) & !(align - 1)
+}
+
+impl<T: ?Sized> ArcData<T> {
+    /// Allocates an `ArcData` with both counters at 1 and `data` left uninitialized.
+    ///
+    /// `to_ptr` attaches `T`'s pointer metadata to the fresh allocation.
+    unsafe fn allocate<A: Allocator>(
+        value: Layout,
+        alloc: &A,
+        to_ptr: impl FnOnce(*mut u8) -> *mut ArcData<T>,
+    ) -> NonNull<ArcData<T>> {
+        let layout = arc_data_layout(value);
+        let Ok(mem) = alloc.allocate(layout) else {
+            alloc::handle_alloc_error(layout);
+        };
+        let ptr = to_ptr(mem.as_ptr());
+        (&raw mut (*ptr).data_ref_count).write(Count::new(1));
+        (&raw mut (*ptr).alloc_ref_count).write(Count::new(1));
+        let ptr = NonNull::new_unchecked(ptr);
+        #[cfg(feature = "leak-tracking")]
+        leaks::register(ptr);
+        ptr
+    }
+
+    /// Recovers the `ArcData` pointer from a pointer to its `data` field,
+    /// given the alignment of `T`.
+    unsafe fn from_data_ptr(data: *const T, align: usize) -> NonNull<ArcData<T>> {
+        let offset = data_offset(align);
+        NonNull::new_unchecked(data.byte_sub(offset) as *mut ArcData<T>)
+    }
+
+    /// Leaves the layout of `data` behind once the data is dropped or moved
+    /// out, for the last `WeakMake` to free the allocation with.
+    ///
+    /// The data can't be looked at anymore by then, and the header has no
+    /// room for a `Layout`, so it goes into the space that is now dead: the
+    /// `data` field if it is large enough, otherwise `data_ref_count`.
+    unsafe fn set_dropped(ptr: *mut ArcData<T>, value: Layout) {
+        let count = if value.size() >= mem::size_of::<Layout>() {
+            (&raw mut (*ptr).data)
+                .cast::<Layout>()
+                .write_unaligned(value);
+            0
+        } else {
+            // size 는 16 미만, align 은 2^29 이하
+            DROPPED | (value.align().trailing_zeros() as CountInt) << 8 | value.size() as CountInt
+        };
+        // upgrade 는 0 과 마찬가지로 None: strong_of
+        (*ptr).data_ref_count.store(count, Relaxed);
+    }
+
+    /// The layout stored by [`ArcData::set_dropped`].
+    unsafe fn dropped_layout(ptr: *mut ArcData<T>) -> Layout {
+        let n = (*ptr).data_ref_count.load(Relaxed);
+        if n == 0 {
+            (&raw const (*ptr).data).cast::<Layout>().read_unaligned()
+        } else {
+            let size = to_usize(n & 0xff);
+            let align = 1 << ((n & !DROPPED) >> 8);
+            Layout::from_size_align_unchecked(size, align)
+        }
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Types whose alignment is known from a raw pointer alone, without reading
+/// the value behind it: sized types, slices and `str`.
+///
+/// [`WeakMake::from_raw`] needs it to find the counters in front of data that
+/// may already be dropped. `dyn Trait` can't be supported on stable Rust.
+pub trait RawAlign: sealed::Sealed {
+    #[doc(hidden)]
+    fn align_of_raw(ptr: *const Self) -> usize;
+}
+
+impl<T> sealed::Sealed for T {}
+impl<T> RawAlign for T {
+    fn align_of_raw(_: *const T) -> usize {
+        mem::align_of::<T>()
+    }
+}
+
+impl<T> sealed::Sealed for [T] {}
+impl<T> RawAlign for [T] {
+    fn align_of_raw(_: *const [T]) -> usize {
+        mem::align_of::<T>()
+    }
+}
+
+impl sealed::Sealed for str {}
+impl RawAlign for str {
+    fn align_of_raw(_: *const str) -> usize {
+        1
+    }
+}
+
+/// Non-owning pointer to the data of an [`ArcMake`].
+pub struct WeakMake<T: ?Sized, A: Allocator = Global> {
+    ptr: NonNull<ArcData<T>>,
+    // 마지막 WeakMake 가 이 allocator 로 메모리를 돌려줌
+    alloc: A,
+}
+
+/// Thread-safe reference counted pointer that can hand out [`WeakMake`]s.
+///
+/// Unsized data is supported: `ArcMake<str>` and `ArcMake<[T]>` come from
+/// the `From`/`FromIterator` impls, and [`unsize_arc_make!`] turns an
+/// `ArcMake<T>` into e.g. an `ArcMake<dyn Trait>`.
+///
+/// Memory comes from the [`Global`] allocator unless the `ArcMake` is
+/// created with [`ArcMake::new_in`].
+///
+/// [`unsize_arc_make!`]: crate::unsize_arc_make
+pub struct ArcMake<T: ?Sized, A: Allocator = Global> {
+    weak: WeakMake<T, A>,
+}
+
+unsafe impl<T: ?Sized + Send + Sync, A: Allocator + Send> Send for WeakMake<T, A> {}
+unsafe impl<T: ?Sized + Send + Sync, A: Allocator + Sync> Sync for WeakMake<T, A> {}
+
+/// Storage for an `ArcMake` in a `static`, created in a `const` context.
+///
+/// `ArcMake`s from [`ArcMake::from_static`] are immortal: the data is never
+/// dropped, and clone/drop only read the counters instead of writing them,
+/// so hot-path clones of a global don't contend on its cache line.
+///
+/// ```
+/// use atomic_and_locks::arc::{ArcMake, StaticArcMake};
+///
+/// static TABLE: StaticArcMake<[u8; 3]> = StaticArcMake::new([1, 2, 3]);
+///
+/// let a = ArcMake::from_static(&TABLE);
+/// let b = a.clone();
+/// assert_eq!(b[..], [1, 2, 3]);
+/// ```
+pub struct StaticArcMake<T> {
+    data: ArcData<T>,
+}
+
+unsafe impl<T: Send + Sync> Sync for StaticArcMake<T> {}
+
+impl<T> StaticArcMake<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            data: ArcData {
+                data_ref_count: Count::new(IMMORTAL),
+                alloc_ref_count: Count::new(IMMORTAL),
+                data: UnsafeCell::new(ManuallyDrop::new(value)),
+            },
+        }
+    }
+}
+
+impl<T> ArcMake<T> {
+    pub fn new(data: T) -> ArcMake<T> {
+        ArcMake::new_in(data, Global)
+    }
+
+    /// Returns an immortal `ArcMake` backed by a static (see [`StaticArcMake`]).
+    pub fn from_static(data: &'static StaticArcMake<T>) -> ArcMake<T> {
+        ArcMake {
+            weak: WeakMake {
+                ptr: NonNull::from(&data.data),
+                alloc: Global,
+            },
+        }
+    }
+
+    /// Creates an immortal `ArcMake` on the heap, for values only known at
+    /// run time such as a lazily initialized global.
+    ///
+    /// The allocation is leaked on purpose: the data is never dropped and
+    /// clone/drop skip the atomic read-modify-write operations.
+    pub fn new_immortal(data: T) -> ArcMake<T> {
+        let arc = ArcMake::new(data);
+        let inner = arc.weak.data();
+        inner.data_ref_count.store(IMMORTAL, Relaxed);
+        inner.alloc_ref_count.store(IMMORTAL, Relaxed);
+        // 의도한 누수라 보고하지 않음
+        #[cfg(feature = "leak-tracking")]
+        leaks::unregister(arc.weak.ptr);
+        arc
+    }
+
+    /// Creates an `ArcMake` whose data can hold a `WeakMake` to itself.
+    ///
+    /// `f` gets a `WeakMake` to the allocation being built; upgrading it
+    /// returns `None` until `new_cyclic` returns.
+    pub fn new_cyclic(f: impl FnOnce(&WeakMake<T>) -> T) -> ArcMake<T> {
+        let ptr = unsafe { ArcData::<T>::allocate(Layout::new::<T>(), &Global, |mem| mem.cast()) };
+        // 생성 중: data 가 없으니 upgrade 불가능
+        // f 가 panic 하면 이 WeakMake 가 여기 남긴 layout 으로 allocation 을 해제
+        unsafe { ArcData::set_dropped(ptr.as_ptr(), Layout::new::<T>()) };
+        let weak = WeakMake { ptr, alloc: Global };
+        let data = f(&weak);
+        unsafe {
+            (&raw mut (*ptr.as_ptr()).data).cast::<T>().write(data);
+            // upgrade 의 Acquire 와 짝: data 쓰기가 먼저 보임
+            (*ptr.as_ptr()).data_ref_count.store(1, Release);
+        }
+        ArcMake { weak }
+    }
+}
+
+impl<T, A: Allocator> ArcMake<T, A> {
+    /// Creates an `ArcMake` whose memory comes from `alloc`.
+    ///
+    /// The allocation goes back to `alloc` when the last `ArcMake` or
+    /// `WeakMake` is dropped.
+    pub fn new_in(data: T, alloc: A) -> ArcMake<T, A> {
+        unsafe {
+            let ptr = ArcData::<T>::allocate(Layout::new::<T>(), &alloc, |mem| mem.cast());
+            (&raw mut (*ptr.as_ptr()).data).cast::<T>().write(data);
+            ArcMake {
+                weak: WeakMake { ptr, alloc },
+            }
+        }
+    }
+
+    /// Returns the data if this is the only `ArcMake`, otherwise gives it back.
+    ///
+    /// Remaining `WeakMake`s can no longer be upgraded afterwards.
+    pub fn try_unwrap(arc: Self) -> Result<T, Self> {
+        if arc
+            .weak
+            .data()
+            .data_ref_count
+            .compare_exchange(1, 0, Relaxed, Relaxed)
+            .is_err()
+        {
+            return Err(arc);
+        }
+        fence(Acquire);
+        // 안전함: data_ref_count 가 0 이므로 data 에 접근하는 건 arc 뿐
+        Ok(unsafe { Self::take_data(arc) })
+    }
+
+    /// Returns the data if this was the last `ArcMake`.
+    ///
+    /// Unlike `try_unwrap(arc).ok()`, when several threads call this on
+    /// their clones at the same time exactly one of them gets `Some`.
+    pub fn into_inner(arc: Self) -> Option<T> {
+        if is_immortal(&arc.weak.data().data_ref_count) {
+            return None;
+        }
+        let arc = ManuallyDrop::new(arc);
+        if arc.weak.data().data_ref_count.fetch_sub(1, Release) != 1 {
+            // 다른 ArcMake 가 남음: WeakMake 몫만 drop
+            drop(unsafe { ptr::read(&arc.weak) });
+            return None;
+        }
+        fence(Acquire);
+        Some(unsafe { Self::take_data(ManuallyDrop::into_inner(arc)) })
+    }
+
+    /// Returns the data if this is the only `ArcMake`, otherwise clones it.
+    pub fn unwrap_or_clone(arc: Self) -> T
+    where
+        T: Clone,
+    {
+        Self::try_unwrap(arc).unwrap_or_else(|arc| (*arc).clone())
+    }
+
+    /// Moves the data out of an `ArcMake` whose `data_ref_count` already hit 0.
+    unsafe fn take_data(arc: Self) -> T {
+        let arc = ManuallyDrop::new(arc);
+        let data = ManuallyDrop::take(&mut *arc.weak.data().data.get());
+        ArcData::set_dropped(arc.weak.ptr.as_ptr(), Layout::new::<T>());
+        drop(ptr::read(&arc.weak));
+        data
+    }
+
+    /// Returns a mutable reference, cloning the data first if it is shared.
+    ///
+    /// If other `ArcMake`s exist the data is cloned into a new allocation.
+    /// If only `WeakMake`s remain the data is moved out instead and those
+    /// `WeakMake`s can no longer be upgraded.
+    pub fn make_mut(arc: &mut Self) -> &mut T
+    where
+        T: Clone,
+        A: Clone,
+    {
+        if arc
+            .weak
+            .data()
+            .data_ref_count
+            .compare_exchange(1, 0, Acquire, Relaxed)
+            .is_err()
+        {
+            // 다른 ArcMake 존재: data 복제
+            *arc = ArcMake::new_in((**arc).clone(), arc.weak.alloc.clone());
+        } else if arc.weak.data().alloc_ref_count.load(Relaxed) != 1 {
+            // WeakMake 만 남음: data_ref_count 가 0 이므로 upgrade 불가능
+            // data 를 새 allocation 으로 옮기고 남은 WeakMake 와 분리
+            let data = unsafe {
+                let data = ManuallyDrop::take(&mut *arc.weak.data().data.get());
+                ArcData::set_dropped(arc.weak.ptr.as_ptr(), Layout::new::<T>());
+                data
+            };
+            let alloc = arc.weak.alloc.clone();
+            let old = ManuallyDrop::new(mem::replace(arc, ArcMake::new_in(data, alloc)));
+            // data_ref_count 는 이미 0: WeakMake 몫만 drop
+            drop(unsafe { ptr::read(&old.weak) });
+        } else {
+            // 유일한 참조: 다른 ArcMake, WeakMake 없음
+            arc.weak.data().data_ref_count.store(1, Release);
+        }
+        // 안전함: 이제 arc 가 data 를 독점
+        unsafe { &mut *arc.weak.data().data.get() }
+    }
+}
+
+impl<T: ?Sized, A: Allocator> ArcMake<T, A> {
+    /// Returns a mutable reference if there are no other `ArcMake`s or `WeakMake`s.
+    pub fn get_mut(arc: &mut Self) -> Option<&mut T> {
+        if arc.weak.data().alloc_ref_count.load(Relaxed) == 1 {
+            fence(Acquire);
+            // 안전함: Arc가 단 한개 존제, Weak 는 한개도 없어
+            // 현재 Arc가 독점적 접근 가능
+            let arcdata = unsafe { arc.weak.ptr.as_mut() };
+            Some(arcdata.data.get_mut())
+        } else {
+            None
+        }
+    }
+
+    pub fn downgrade(arc: &Self) -> WeakMake<T, A>
+    where
+        A: Clone,
+    {
+        arc.weak.clone()
+    }
+
+    /// Like `clone`, but returns an error instead of aborting when the
+    /// count would go past [`MAX_REFCOUNT`].
+    pub fn try_clone(arc: &Self) -> Result<Self, RefCountOverflow>
+    where
+        A: Clone,
+    {
+        let weak = arc.weak.try_clone()?;
+        // 실패하면 weak 의 drop 이 alloc_ref_count 를 되돌림
+        try_increment(&weak.data().data_ref_count)?;
+        Ok(ArcMake { weak })
+    }
+
+    /// The allocator the data was allocated with.
+    pub fn allocator(arc: &Self) -> &A {
+        &arc.weak.alloc
+    }
+
+    /// Number of `ArcMake`s sharing this allocation (`data_ref_count`).
+    ///
+    /// The load is `Acquire`: if the count was lowered by an `ArcMake` dropped
+    /// on another thread, everything that thread did before the drop is
+    /// visible afterwards. Other threads may change the count at any time,
+    /// so it is only a snapshot.
+    ///
+    /// Immortal `ArcMake`s report a count above [`MAX_REFCOUNT`].
+    pub fn strong_count(arc: &Self) -> usize {
+        to_usize(arc.weak.data().data_ref_count.load(Acquire))
+    }
+
+    /// Number of `WeakMake`s pointing to this allocation.
+    ///
+    /// `alloc_ref_count` also holds one reference per `ArcMake`, which is
+    /// subtracted here. Both counters are read with `Acquire` loads, but not
+    /// as one atomic snapshot: while other threads clone, upgrade or drop,
+    /// the result may be off by the references in flight.
+    pub fn weak_count(arc: &Self) -> usize {
+        arc.weak.weak_count()
+    }
+
+    /// Whether both `ArcMake`s point to the same allocation.
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        ptr::addr_eq(a.weak.ptr.as_ptr(), b.weak.ptr.as_ptr())
+    }
+
+    /// Pointer to the data, valid for as long as some `ArcMake` is alive.
+    pub fn as_ptr(arc: &Self) -> *const T {
+        arc.weak.as_ptr()
+    }
+
+    /// Converts to an `ArcMake<U>` sharing the same allocation.
+    ///
+    /// Prefer [`unsize_arc_make!`](crate::unsize_arc_make), which supplies `cast`.
+    ///
+    /// # Safety
+    ///
+    /// `cast` must return its argument unchanged apart from the pointer
+    /// metadata, as an unsizing coercion such as `*const T` to
+    /// `*const dyn Trait` does.
+    pub unsafe fn unsize<U: ?Sized>(
+        arc: Self,
+        cast: impl FnOnce(*const T) -> *const U,
+    ) -> ArcMake<U, A> {
+        let arc = ManuallyDrop::new(arc);
+        let data = cast(Self::as_ptr(&arc));
+        ArcMake {
+            weak: WeakMake {
+                // 안전함: arc 가 있으므로 data 는 살아 있음
+                ptr: ArcData::from_data_ptr(data, mem::align_of_val(&*data)),
+                alloc: ptr::read(&arc.weak.alloc),
+            },
+        }
+    }
+}
+
+// raw pointer 에는 allocator 를 담을 곳이 없음: Global 만
+impl<T: ?Sized> ArcMake<T> {
+    /// Consumes the `ArcMake` without touching the counters.
+    ///
+    /// The pointer points at the data, not the counters, and must be passed
+    /// back to [`ArcMake::from_raw`] to avoid a leak.
+    pub fn into_raw(arc: Self) -> *const T {
+        let ptr = Self::as_ptr(&arc);
+        mem::forget(arc);
+        ptr
+    }
+
+    /// Takes back ownership of a pointer returned by [`ArcMake::into_raw`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from `ArcMake::<T>::into_raw`, and its strong count is
+    /// taken over by the returned `ArcMake`.
+    pub unsafe fn from_raw(ptr: *const T) -> Self {
+        ArcMake {
+            weak: WeakMake {
+                // 안전함: strong count 를 넘겨받으므로 data 는 살아 있음
+                ptr: ArcData::from_data_ptr(ptr, mem::align_of_val(&*ptr)),
+                alloc: Global,
+            },
+        }
+    }
+
+    /// Adds a strong reference to the `ArcMake` behind `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from [`ArcMake::into_raw`] and its `ArcMake` must
+    /// still be alive.
+    pub unsafe fn increment_strong_count(ptr: *const T) {
+        let arc = ManuallyDrop::new(Self::from_raw(ptr));
+        let _clone: ManuallyDrop<Self> = arc.clone();
+    }
+
+    /// Drops a strong reference to the `ArcMake` behind `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from [`ArcMake::into_raw`] and the strong reference
+    /// given up here must be one the caller owns.
+    pub unsafe fn decrement_strong_count(ptr: *const T) {
+        drop(Self::from_raw(ptr));
+    }
+}
+
+/// Unsizes an `ArcMake<T>` into an `ArcMake<U>`, e.g. `ArcMake<dyn Trait>`.
+///
+/// ```
+/// use atomic_and_locks::arc::ArcMake;
+/// use atomic_and_locks::unsize_arc_make;
+/// use std::fmt::Display;
+///
+/// let a = unsize_arc_make!(ArcMake::new(5) => dyn Display);
+/// assert_eq!(a.to_string(), "5");
+/// ```
+///
+/// The argument is evaluated outside the macro's `unsafe` block:
+///
+/// ```compile_fail,E0133
+/// # use atomic_and_locks::arc::ArcMake;
+/// # use atomic_and_locks::unsize_arc_make;
+/// # use std::fmt::Display;
+/// unsafe fn make() -> ArcMake<i32> {
+///     ArcMake::new(5)
+/// }
+/// let a = unsize_arc_make!(make() => dyn Display);
+/// ```
+#[macro_export]
+macro_rules! unsize_arc_make {
+    ($arc:expr => $ty:ty) => {{
+        // unsafe 블록 밖에서 평가: 인자 안의 unsafe 호출을 허용하지 않음
+        let arc = $arc;
+        // 안전함: 암묵적 unsizing coercion 은 주소를 바꾸지 않음
+        unsafe {
+            $crate::arc::ArcMake::unsize(arc, |p| {
+                let p: *const $ty = p;
+                p
+            })
+        }
+    }};
+}
+
+impl<T> From<Vec<T>> for ArcMake<[T]> {
+    fn from(mut v: Vec<T>) -> ArcMake<[T]> {
+        let len = v.len();
+        unsafe {
+            let ptr = ArcData::<[T]>::allocate(Layout::array::<T>(len).unwrap(), &Global, |mem| {
+                ptr::slice_from_raw_parts_mut(mem.cast::<T>(), len) as *mut ArcData<[T]>
+            });
+            let dst = (&raw mut (*ptr.as_ptr()).data).cast::<T>();
+            ptr::copy_nonoverlapping(v.as_ptr(), dst, len);
+            // 원소는 옮겨졌으니 버퍼만 해제
+            v.set_len(0);
+            ArcMake {
+                weak: WeakMake { ptr, alloc: Global },
+            }
+        }
+    }
+}
+
+impl<T: Clone> From<&[T]> for ArcMake<[T]> {
+    fn from(s: &[T]) -> ArcMake<[T]> {
+        ArcMake::from(s.to_vec())
+    }
+}
+
+impl<T> FromIterator<T> for ArcMake<[T]> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> ArcMake<[T]> {
+        ArcMake::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl From<&str> for ArcMake<str> {
+    fn from(s: &str) -> ArcMake<str> {
+        let bytes = ManuallyDrop::new(ArcMake::<[u8]>::from(s.as_bytes()));
+        // 안전함: str 과 [u8] 은 layout 이 같고 내용은 UTF-8
+        let ptr = bytes.weak.ptr.as_ptr() as *mut ArcData<str>;
+        ArcMake {
+            weak: WeakMake {
+                ptr: unsafe { NonNull::new_unchecked(ptr) },
+                alloc: Global,
+            },
+        }
+    }
+}
+
+impl From<String> for ArcMake<str> {
+    fn from(s: String) -> ArcMake<str> {
+        ArcMake::from(&s[..])
+    }
+}
+
+// WeakMake::new() 가 쓰는 주소: 할당 없음
+const DANGLING: usize = usize::MAX;
+
+fn is_dangling<T: ?Sized>(ptr: *const T) -> bool {
+    ptr.cast::<()>().addr() == DANGLING
+}
+
+impl<T> WeakMake<T> {
+    /// Creates a `WeakMake` without an allocation that never upgrades.
+    pub const fn new() -> WeakMake<T> {
+        WeakMake {
+            ptr: unsafe { NonNull::new_unchecked(ptr::without_provenance_mut(DANGLING)) },
+            alloc: Global,
+        }
+    }
+}
+
+impl<T> Default for WeakMake<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized, A: Allocator> WeakMake<T, A> {
+    // ArcMake 안의 WeakMake 는 dangling 일 수 없음
+    fn data(&self) -> &ArcData<T> {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Counters only: `data` may already have been dropped, so no reference
+    /// to it is made.
+    fn inner(&self) -> Option<&ArcData<()>> {
+        if is_dangling(self.ptr.as_ptr()) {
+            None
+        } else {
+            // 안전함: repr(C) 라 data 앞의 필드 위치는 T 와 상관없음
+            Some(unsafe { self.ptr.cast::<ArcData<()>>().as_ref() })
+        }
+    }
+
+    /// Pointer to the data, which may already have been dropped.
+    ///
+    /// For a `WeakMake` from [`WeakMake::new`] the pointer is dangling.
+    pub fn as_ptr(&self) -> *const T {
+        let ptr = self.ptr.as_ptr();
+        if is_dangling(ptr) {
+            return ptr as *const T;
+        }
+        // 참조를 만들지 않고 주소만 계산
+        unsafe { (&raw const (*ptr).data) as *const T }
+    }
+
+    /// Number of `ArcMake`s for this allocation, 0 for [`WeakMake::new`].
+    ///
+    /// Same ordering guarantees as [`ArcMake::strong_count`].
+    pub fn strong_count(&self) -> usize {
+        self.inner().map_or(0, |inner| {
+            to_usize(strong_of(inner.data_ref_count.load(Acquire)))
+        })
+    }
+
+    /// Number of `WeakMake`s for this allocation, 0 for [`WeakMake::new`].
+    ///
+    /// Same caveats as [`ArcMake::weak_count`].
+    pub fn weak_count(&self) -> usize {
+        self.inner().map_or(0, |inner| {
+            let alloc = inner.alloc_ref_count.load(Acquire);
+            let data = strong_of(inner.data_ref_count.load(Acquire));
+            // ArcMake 도 각자 WeakMake 를 하나씩 가짐
+            to_usize(alloc.saturating_sub(data))
+        })
+    }
+
+    /// Whether both point to the same allocation, or both are dangling.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        ptr::addr_eq(self.ptr.as_ptr(), other.ptr.as_ptr())
+    }
+
+    /// Like `clone`, but returns an error instead of aborting when the
+    /// count would go past [`MAX_REFCOUNT`].
+    pub fn try_clone(&self) -> Result<Self, RefCountOverflow>
+    where
+        A: Clone,
+    {
+        if let Some(inner) = self.inner() {
+            try_increment(&inner.alloc_ref_count)?;
+        }
+        Ok(WeakMake {
+            ptr: self.ptr,
+            alloc: self.alloc.clone(),
+        })
+    }
+
+    /// Returns `None` once every `ArcMake` has been dropped.
+    pub fn upgrade(&self) -> Option<ArcMake<T, A>>
+    where
+        A: Clone,
+    {
+        let inner = self.inner()?;
+        let mut n = inner.data_ref_count.load(Relaxed);
+
+        loop {
+            if n == IMMORTAL {
+                return Some(ArcMake { weak: self.clone() });
+            }
+            if strong_of(n) == 0 {
+                return None;
+            }
+            // 증가 전에 검사하므로 panic 해도 counter 는 그대로
+            assert!(n < MAX_COUNT, "reference count overflow");
+            // Acquire: new_cyclic 이 끝나기 전의 data 쓰기와 동기화
+            if let Err(e) = inner
+                .data_ref_count
+                .compare_exchange_weak(n, n + 1, Acquire, Relaxed)
+            {
+                n = e;
+                continue;
+            }
+            return Some(ArcMake { weak: self.clone() });
+        }
+    }
+}
+
+impl<T: ?Sized> WeakMake<T> {
+    /// Consumes the `WeakMake` without touching the counters.
+    pub fn into_raw(self) -> *const T {
+        let ptr = self.as_ptr();
+        mem::forget(self);
+        ptr
+    }
+}
+
+// data 가 drop 됐을 수 있어 align_of_val 을 쓸 수 없음: align 은 RawAlign 으로
+impl<T: ?Sized + RawAlign> WeakMake<T> {
+    /// Takes back ownership of a pointer returned by [`WeakMake::into_raw`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from `WeakMake::<T>::into_raw`, and its weak
+    /// reference is taken over by the returned `WeakMake`.
+    pub unsafe fn from_raw(ptr: *const T) -> Self {
+        if is_dangling(ptr) {
+            return WeakMake {
+                ptr: NonNull::new_unchecked(ptr as *mut ArcData<T>),
+                alloc: Global,
+            };
+        }
+        WeakMake {
+            ptr: ArcData::from_data_ptr(ptr, T::align_of_raw(ptr)),
+            alloc: Global,
+        }
+    }
+
+    /// Adds a weak reference to the `WeakMake` behind `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from [`WeakMake::into_raw`] and its `WeakMake` must
+    /// still be alive.
+    pub unsafe fn increment_weak_count(ptr: *const T) {
+        let weak = ManuallyDrop::new(Self::from_raw(ptr));
+        let _clone: ManuallyDrop<Self> = weak.clone();
+    }
+
+    /// Drops a weak reference to the `WeakMake` behind `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must come from [`WeakMake::into_raw`] and the weak reference
+    /// given up here must be one the caller owns.
+    pub unsafe fn decrement_weak_count(ptr: *const T) {
+        drop(Self::from_raw(ptr));
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Deref for ArcMake<T, A> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        let ptr = self.weak.data().data.get();
+        // 안전함 Arc가 data를 가리키고 있어
+        // data는 존재하고 공유될수 있다
+        // Option 이 아니라 unwrap 없이 포인터 offset 하나 (benches/arc_layout.rs)
+        unsafe { &*ptr }
+    }
+}
+
+impl<T: ?Sized, A: Allocator + Clone> Clone for WeakMake<T, A> {
+    fn clone(&self) -> Self {
+        if let Some(inner) = self.inner() {
+            increment(&inner.alloc_ref_count);
+        }
+        WeakMake {
+            ptr: self.ptr,
+            alloc: self.alloc.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized, A: Allocator + Clone> Clone for ArcMake<T, A> {
+    fn clone(&self) -> Self {
+        let weak = self.weak.clone();
+        increment(&weak.data().data_ref_count);
+        ArcMake { weak }
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Drop for WeakMake<T, A> {
+    fn drop(&mut self) {
+        let Some(inner) = self.inner() else {
+            return;
+        };
+        if is_immortal(&inner.alloc_ref_count) {
+            return;
+        }
+        if inner.alloc_ref_count.fetch_sub(1, Release) == 1 {
+            fence(Acquire);
+            // 안전함: data_ref_count 가 0 이 될 때 data 를 치우며 layout 을 남겨 둠
+            let layout = arc_data_layout(unsafe { ArcData::dropped_layout(self.ptr.as_ptr()) });
+            #[cfg(feature = "leak-tracking")]
+            leaks::unregister(self.ptr);
+            // 안전함: new_in 에서 같은 allocator, 같은 layout 으로 할당
+            unsafe {
+                self.alloc.deallocate(self.ptr.cast(), layout);
+            }
+        }
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Drop for ArcMake<T, A> {
+    fn drop(&mut self) {
+        let count = &self.weak.data().data_ref_count;
+        if is_immortal(count) {
+            // WeakMake 몫도 immortal: 그쪽 drop 도 아무것도 안 함
+            return;
+        }
+        if count.fetch_sub(1, Release) == 1 {
+            fence(Acquire);
+            let ptr = self.weak.data().data.get();
+            // 안정함: data의 레퍼런스 카운터가 0 이므로
+            // 이제 data 접근 불가능
+            unsafe {
+                let layout = Layout::for_value::<T>(&*ptr);
+                ManuallyDrop::drop(&mut *ptr);
+                ArcData::set_dropped(self.weak.ptr.as_ptr(), layout);
+            }
+        }
+    }
+}
+
+// 아래는 전부 data 로 넘기는 impl
+
+impl<T: ?Sized + fmt::Debug, A: Allocator> fmt::Debug for ArcMake<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Display, A: Allocator> fmt::Display for ArcMake<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+/// Prints the address of the data, like `{:p}` on a `&T`.
+impl<T: ?Sized, A: Allocator> fmt::Pointer for ArcMake<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&Self::as_ptr(self), f)
+    }
+}
+
+// data 는 이미 drop 됐을 수 있어 내용을 출력하지 않음
+impl<T: ?Sized, A: Allocator> fmt::Debug for WeakMake<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(Weak)")
+    }
+}
+
+impl<T: ?Sized + PartialEq, A: Allocator> PartialEq for ArcMake<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: ?Sized + Eq, A: Allocator> Eq for ArcMake<T, A> {}
+
+impl<T: ?Sized + PartialOrd, A: Allocator> PartialOrd for ArcMake<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: ?Sized + Ord, A: Allocator> Ord for ArcMake<T, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: ?Sized + Hash, A: Allocator> Hash for ArcMake<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T: ?Sized, A: Allocator> Borrow<T> for ArcMake<T, A> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ?Sized, A: Allocator> AsRef<T> for ArcMake<T, A> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T: Default> Default for ArcMake<T> {
+    fn default() -> Self {
+        ArcMake::new(T::default())
+    }
+}
+
+impl<T> From<T> for ArcMake<T> {
+    fn from(data: T) -> Self {
+        ArcMake::new(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // MAX_REFCOUNT 번 clone 할 수는 없으니 counter 를 직접 올림
+    fn set_counts<T>(arc: &ArcMake<T>, data: CountInt, alloc: CountInt) {
+        let inner = arc.weak.data();
+        inner.data_ref_count.store(data, Relaxed);
+        inner.alloc_ref_count.store(alloc, Relaxed);
+    }
+
+    fn counts<T>(arc: &ArcMake<T>) -> (usize, usize) {
+        (ArcMake::strong_count(arc), ArcMake::weak_count(arc))
+    }
+
+    #[test]
+    fn header_size() {
+        assert_eq!(
+            mem::size_of::<ArcData<()>>(),
+            2 * mem::size_of::<CountInt>()
+        );
+    }
+
+    #[test]
+    fn try_clone_at_limit() {
+        let a = ArcMake::new(1);
+        set_counts(&a, MAX_COUNT - 1, MAX_COUNT - 1);
+
+        let b = ArcMake::try_clone(&a).unwrap();
+        assert_eq!(counts(&a), (MAX_REFCOUNT, 0));
+        assert_eq!(ArcMake::try_clone(&a).err(), Some(RefCountOverflow));
+        assert_eq!(counts(&a), (MAX_REFCOUNT, 0));
+        drop(b);
+
+        // strong count 만 한도: 먼저 올린 alloc_ref_count 는 되돌아감
+        set_counts(&a, MAX_COUNT, MAX_COUNT - 1);
+        assert_eq!(ArcMake::try_clone(&a).err(), Some(RefCountOverflow));
+        assert_eq!(
+            to_usize(a.weak.data().alloc_ref_count.load(Relaxed)),
+            MAX_REFCOUNT - 1
+        );
+
+        set_counts(&a, 1, 1);
+        assert_eq!(counts(&a), (1, 0));
+    }
+
+    #[test]
+    fn weak_try_clone_at_limit() {
+        let a = ArcMake::new(1);
+        let w = ArcMake::downgrade(&a);
+        set_counts(&a, 1, MAX_COUNT);
+
+        assert_eq!(w.try_clone().err(), Some(RefCountOverflow));
+        assert_eq!(ArcMake::try_clone(&a).err(), Some(RefCountOverflow));
+        assert_eq!(counts(&a), (1, MAX_REFCOUNT - 1));
+
+        set_counts(&a, 1, 2);
+        assert!(w.try_clone().is_ok());
+        assert!(WeakMake::<i32>::new().try_clone().is_ok());
+    }
+
+    #[test]
+    fn upgrade_at_limit() {
+        let a = ArcMake::new(1);
+        let w = ArcMake::downgrade(&a);
+        set_counts(&a, MAX_COUNT, MAX_COUNT + 1);
+
+        let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| w.upgrade()));
+        assert!(r.is_err());
+        // 검사가 증가보다 먼저라 counter 는 그대로
+        assert_eq!(counts(&a), (MAX_REFCOUNT, 1));
+
+        set_counts(&a, 1, 2);
+    }
+}
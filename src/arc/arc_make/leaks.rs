@@ -0,0 +1,130 @@
+//! Registry of live `ArcData` allocations (feature `leak-tracking`).
+//!
+//! Every allocation is registered with a creation backtrace and removed right
+//! before it is freed, so whatever is left at exit was leaked, usually by an
+//! `ArcMake` cycle that should have used a `WeakMake`.
+
+use super::{strong_of, to_usize, ArcData};
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::ptr::NonNull;
+use std::sync::atomic::Ordering::Acquire;
+use std::sync::{Arc, Mutex, MutexGuard, Once, TryLockError};
+
+struct Entry {
+    id: u64,
+    type_name: &'static str,
+    backtrace: Arc<Backtrace>,
+}
+
+#[derive(Default)]
+struct Registry {
+    next_id: u64,
+    // key 는 ArcData 의 주소
+    entries: HashMap<usize, Entry>,
+}
+
+static REGISTRY: Mutex<Option<Registry>> = Mutex::new(None);
+static REPORT_AT_EXIT: Once = Once::new();
+
+fn registry() -> MutexGuard<'static, Option<Registry>> {
+    // report 중 panic 이 나도 registry 는 계속 사용
+    REGISTRY.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// An `ArcData` allocation that has not been freed yet.
+#[derive(Debug, Clone)]
+pub struct LiveAllocation {
+    /// Address of the allocation (the counters, not the data).
+    pub address: usize,
+    pub type_name: &'static str,
+    pub strong_count: usize,
+    pub weak_count: usize,
+    /// Where the allocation was created.
+    pub backtrace: Arc<Backtrace>,
+}
+
+/// Lists the `ArcMake` allocations still alive, oldest first.
+pub fn live_allocations() -> Vec<LiveAllocation> {
+    collect(&registry())
+}
+
+fn collect(registry: &Option<Registry>) -> Vec<LiveAllocation> {
+    let Some(registry) = registry.as_ref() else {
+        return Vec::new();
+    };
+    let mut live: Vec<_> = registry.entries.iter().collect();
+    live.sort_by_key(|(_, entry)| entry.id);
+    live.into_iter()
+        .map(|(&address, entry)| {
+            // 안전함: unregister 가 같은 lock 을 잡은 뒤에야 해제되고,
+            // repr(C) 라 counter 위치는 T 와 상관없음
+            let header = unsafe { &*(address as *const ArcData<()>) };
+            let data = strong_of(header.data_ref_count.load(Acquire));
+            let alloc = header.alloc_ref_count.load(Acquire);
+            LiveAllocation {
+                address,
+                type_name: entry.type_name,
+                strong_count: to_usize(data),
+                weak_count: to_usize(alloc.saturating_sub(data)),
+                backtrace: entry.backtrace.clone(),
+            }
+        })
+        .collect()
+}
+
+pub(super) fn register<T: ?Sized>(ptr: NonNull<ArcData<T>>) {
+    REPORT_AT_EXIT.call_once(|| unsafe {
+        atexit(report);
+    });
+    let backtrace = Arc::new(Backtrace::force_capture());
+    let mut registry = registry();
+    let registry = registry.get_or_insert_with(Registry::default);
+    let id = registry.next_id;
+    registry.next_id += 1;
+    registry.entries.insert(
+        ptr.cast::<()>().as_ptr().addr(),
+        Entry {
+            id,
+            type_name: std::any::type_name::<T>(),
+            backtrace,
+        },
+    );
+}
+
+pub(super) fn unregister<T: ?Sized>(ptr: NonNull<ArcData<T>>) {
+    if let Some(registry) = registry().as_mut() {
+        registry.entries.remove(&ptr.cast::<()>().as_ptr().addr());
+    }
+}
+
+extern "C" {
+    fn atexit(f: extern "C" fn()) -> i32;
+}
+
+extern "C" fn report() {
+    // exit 시점에 다른 스레드가 lock 을 잡고 있으면 기다리지 않고 보고를 건너뜀
+    let live = match REGISTRY.try_lock() {
+        Ok(registry) => collect(&registry),
+        Err(TryLockError::Poisoned(e)) => collect(&e.into_inner()),
+        Err(TryLockError::WouldBlock) => return,
+    };
+    if live.is_empty() {
+        return;
+    }
+    // extern "C" 밖으로 unwind 할 수 없음: stderr 가 닫혀 있어도 panic 하지 않도록 에러 무시
+    let mut stderr = io::stderr().lock();
+    let _ = writeln!(
+        stderr,
+        "ArcMake: {} allocation(s) still alive at exit",
+        live.len()
+    );
+    for leak in live {
+        let _ = writeln!(
+            stderr,
+            "\n{:#x} {} (strong {}, weak {}) created at:\n{}",
+            leak.address, leak.type_name, leak.strong_count, leak.weak_count, leak.backtrace
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! `serde` support (feature `serde`): an `ArcMake` serializes as its data.
+//!
+//! Sharing is not preserved: two `ArcMake`s to the same data serialize the
+//! data twice and deserialize into separate allocations.
+
+use super::ArcMake;
+use crate::arc::allocator::Allocator;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl<T: ?Sized + Serialize, A: Allocator> Serialize for ArcMake<T, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ArcMake<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(ArcMake::new)
+    }
+}
+
+// ArcMake<str>, ArcMake<[T]>: 한 번 Box/Vec 으로 받은 뒤 옮김
+impl<'de> Deserialize<'de> for ArcMake<str> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(ArcMake::from)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ArcMake<[T]> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<T>::deserialize(deserializer).map(ArcMake::from)
+    }
+}
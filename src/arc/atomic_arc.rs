@@ -0,0 +1,252 @@
+//! Atomically replaceable `ArcMake` slot.
+//!
+//! `load` has to increment the strong count of the pointer it just read,
+//! while a writer may swap that pointer out and drop the last `ArcMake`
+//! in between. Readers therefore register in one of two reader counters
+//! around that window, and writers wait for both counters to drain before
+//! they give up the old value.
+
+use crate::arc::ArcMake;
+use std::marker::PhantomData;
+use std::ptr;
+use std::sync::atomic::Ordering::{Release, SeqCst};
+use std::sync::atomic::{AtomicPtr, AtomicUsize};
+use std::sync::Mutex;
+use std::thread;
+
+/// Reader counters split by epoch, shared by the atomic `ArcMake` cells.
+pub(crate) struct Readers {
+    epoch: AtomicUsize,
+    counts: [AtomicUsize; 2],
+    // writer 끼리는 순서대로
+    writer: Mutex<()>,
+}
+
+impl Readers {
+    pub(crate) const fn new() -> Self {
+        Self {
+            epoch: AtomicUsize::new(0),
+            counts: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Runs `f` while registered as a reader.
+    pub(crate) fn read<R>(&self, f: impl FnOnce() -> R) -> R {
+        let epoch = self.epoch.load(SeqCst) & 1;
+        self.counts[epoch].fetch_add(1, SeqCst);
+        let r = f();
+        self.counts[epoch].fetch_sub(1, Release);
+        r
+    }
+
+    /// Runs `f` as the only writer, then waits until no reader that could
+    /// have seen the previous pointer is still registered.
+    pub(crate) fn write<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _writer = self.writer.lock().unwrap();
+        let r = f();
+        // 두 counter 를 차례로 비움: epoch 를 뒤집으면 새 reader 는
+        // 다른 counter 로 가므로 기다리는 counter 는 결국 0 이 됨
+        for _ in 0..2 {
+            let old = self.epoch.fetch_add(1, SeqCst) & 1;
+            while self.counts[old].load(SeqCst) != 0 {
+                thread::yield_now();
+            }
+        }
+        r
+    }
+}
+
+/// `ArcMake<T>` that can be loaded and replaced atomically, e.g. a global
+/// "current config".
+pub struct AtomicArc<T> {
+    // ArcMake::into_raw 로 얻은 포인터, 이 ArcMake 의 strong count 하나를 소유
+    ptr: AtomicPtr<T>,
+    readers: Readers,
+    _owned: PhantomData<ArcMake<T>>,
+}
+
+impl<T> AtomicArc<T> {
+    pub fn new(arc: ArcMake<T>) -> Self {
+        Self {
+            ptr: AtomicPtr::new(ArcMake::into_raw(arc) as *mut T),
+            readers: Readers::new(),
+            _owned: PhantomData,
+        }
+    }
+
+    /// Returns a new `ArcMake` to the current value.
+    pub fn load(&self) -> ArcMake<T> {
+        self.readers.read(|| {
+            let ptr = self.ptr.load(SeqCst);
+            // 안전함: reader 로 등록된 동안 writer 는 ptr 의 ArcMake 를 drop 하지 않음
+            unsafe {
+                ArcMake::increment_strong_count(ptr);
+                ArcMake::from_raw(ptr)
+            }
+        })
+    }
+
+    pub fn store(&self, new: ArcMake<T>) {
+        drop(self.swap(new));
+    }
+
+    /// Replaces the value and returns the previous one.
+    pub fn swap(&self, new: ArcMake<T>) -> ArcMake<T> {
+        let new = ArcMake::into_raw(new) as *mut T;
+        let old = self.readers.write(|| self.ptr.swap(new, SeqCst));
+        unsafe { ArcMake::from_raw(old) }
+    }
+
+    /// Replaces the value with `new` if it is still `current`.
+    ///
+    /// Returns the previous value on success, or gives `new` back if the
+    /// value was something else.
+    pub fn compare_and_swap(
+        &self,
+        current: &ArcMake<T>,
+        new: ArcMake<T>,
+    ) -> Result<ArcMake<T>, ArcMake<T>> {
+        let current = ArcMake::as_ptr(current) as *mut T;
+        let new = ArcMake::into_raw(new) as *mut T;
+        // current 를 caller 가 들고 있으니 같은 주소가 재사용될 일 없음
+        match self
+            .readers
+            .write(|| self.ptr.compare_exchange(current, new, SeqCst, SeqCst))
+        {
+            Ok(old) => Ok(unsafe { ArcMake::from_raw(old) }),
+            Err(_) => Err(unsafe { ArcMake::from_raw(new) }),
+        }
+    }
+
+    pub fn into_inner(mut self) -> ArcMake<T> {
+        // 안전함: self 를 소유하니 reader 없음, drop 은 null 을 건너뜀
+        let ptr = std::mem::replace(self.ptr.get_mut(), ptr::null_mut());
+        unsafe { ArcMake::from_raw(ptr) }
+    }
+}
+
+impl<T> Drop for AtomicArc<T> {
+    fn drop(&mut self) {
+        let ptr = *self.ptr.get_mut();
+        if !ptr.is_null() {
+            drop(unsafe { ArcMake::from_raw(ptr) });
+        }
+    }
+}
+
+/// `Option<ArcMake<T>>` cell with atomic take, replace and lazy init.
+///
+/// `get_or_init` resolves racing initializers like `get_data()` (p.93):
+/// every racer builds a value, one `compare_exchange` wins and the losers
+/// drop theirs.
+pub struct AtomicOptionArc<T> {
+    // null 이면 None, 아니면 ArcMake::into_raw 로 얻은 포인터
+    ptr: AtomicPtr<T>,
+    readers: Readers,
+    _owned: PhantomData<ArcMake<T>>,
+}
+
+impl<T> AtomicOptionArc<T> {
+    /// Creates an empty cell.
+    pub const fn new() -> Self {
+        Self {
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            readers: Readers::new(),
+            _owned: PhantomData,
+        }
+    }
+
+    pub fn load(&self) -> Option<ArcMake<T>> {
+        self.readers.read(|| {
+            let ptr = self.ptr.load(SeqCst);
+            if ptr.is_null() {
+                return None;
+            }
+            // 안전함: reader 로 등록된 동안 writer 는 ptr 의 ArcMake 를 drop 하지 않음
+            unsafe {
+                ArcMake::increment_strong_count(ptr);
+                Some(ArcMake::from_raw(ptr))
+            }
+        })
+    }
+
+    /// Empties the cell and returns what it held.
+    pub fn take(&self) -> Option<ArcMake<T>> {
+        self.replace(None)
+    }
+
+    /// Puts `new` in the cell and returns what it held.
+    pub fn replace(&self, new: Option<ArcMake<T>>) -> Option<ArcMake<T>> {
+        let new = new.map_or(ptr::null_mut(), |arc| ArcMake::into_raw(arc) as *mut T);
+        let old = self.readers.write(|| self.ptr.swap(new, SeqCst));
+        if old.is_null() {
+            None
+        } else {
+            Some(unsafe { ArcMake::from_raw(old) })
+        }
+    }
+
+    /// Returns the current value, filling an empty cell with `f()` first.
+    ///
+    /// If several threads race to fill the cell, all of them run `f` but
+    /// only one value is stored; the others are dropped.
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> ArcMake<T> {
+        if let Some(arc) = self.load() {
+            return arc;
+        }
+        let new = ArcMake::new(f());
+        let raw = ArcMake::as_ptr(&new) as *mut T;
+        loop {
+            // cell 이 가질 strong count 를 미리 추가
+            unsafe { ArcMake::increment_strong_count(raw) };
+            // 빈 cell 을 채우기만 하므로 기다릴 reader 없음
+            match self
+                .ptr
+                .compare_exchange(ptr::null_mut(), raw, SeqCst, SeqCst)
+            {
+                Ok(_) => return new,
+                Err(_) => {
+                    unsafe { ArcMake::decrement_strong_count(raw) };
+                    // 다른 스레드가 이김: new 는 drop
+                    // 그 사이 take 됐으면 다시 시도
+                    if let Some(winner) = self.load() {
+                        return winner;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn into_inner(mut self) -> Option<ArcMake<T>> {
+        let ptr = std::mem::replace(self.ptr.get_mut(), ptr::null_mut());
+        if ptr.is_null() {
+            None
+        } else {
+            Some(unsafe { ArcMake::from_raw(ptr) })
+        }
+    }
+}
+
+impl<T> Default for AtomicOptionArc<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Option<ArcMake<T>>> for AtomicOptionArc<T> {
+    fn from(arc: Option<ArcMake<T>>) -> Self {
+        let cell = Self::new();
+        cell.replace(arc);
+        cell
+    }
+}
+
+impl<T> Drop for AtomicOptionArc<T> {
+    fn drop(&mut self) {
+        let ptr = *self.ptr.get_mut();
+        if !ptr.is_null() {
+            drop(unsafe { ArcMake::from_raw(ptr) });
+        }
+    }
+}
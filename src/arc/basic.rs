@@ -0,0 +1,102 @@
+//! Minimal `ArcMake` with a single reference counter and no weak pointers (p.145).
+
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::ptr::NonNull;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use std::sync::atomic::{fence, AtomicUsize};
+
+struct ArcData<T> {
+    ref_count: AtomicUsize,
+    data: T,
+}
+
+/// Thread-safe reference counted pointer.
+pub struct ArcMake<T> {
+    ptr: NonNull<ArcData<T>>,
+}
+
+unsafe impl<T: Send + Sync> Send for ArcMake<T> {}
+unsafe impl<T: Send + Sync> Sync for ArcMake<T> {}
+
+impl<T> ArcMake<T> {
+    pub fn new(data: T) -> ArcMake<T> {
+        ArcMake {
+            ptr: NonNull::from(Box::leak(Box::new(ArcData {
+                ref_count: AtomicUsize::new(1),
+                data,
+            }))),
+        }
+    }
+
+    /// Returns the data if this is the only `ArcMake`, otherwise gives it back.
+    pub fn try_unwrap(arc: Self) -> Result<T, Self> {
+        if arc
+            .data()
+            .ref_count
+            .compare_exchange(1, 0, Relaxed, Relaxed)
+            .is_err()
+        {
+            return Err(arc);
+        }
+        fence(Acquire);
+        let arc = ManuallyDrop::new(arc);
+        // 안전함: 마지막 ArcMake
+        Ok(unsafe { Box::from_raw(arc.ptr.as_ptr()) }.data)
+    }
+
+    /// Returns the data if this was the last `ArcMake`.
+    ///
+    /// When several threads call this on their clones at the same time
+    /// exactly one of them gets `Some`.
+    pub fn into_inner(arc: Self) -> Option<T> {
+        let arc = ManuallyDrop::new(arc);
+        if arc.data().ref_count.fetch_sub(1, Release) != 1 {
+            return None;
+        }
+        fence(Acquire);
+        // 안전함: 마지막 ArcMake
+        Some(unsafe { Box::from_raw(arc.ptr.as_ptr()) }.data)
+    }
+
+    /// Returns the data if this is the only `ArcMake`, otherwise clones it.
+    pub fn unwrap_or_clone(arc: Self) -> T
+    where
+        T: Clone,
+    {
+        Self::try_unwrap(arc).unwrap_or_else(|arc| (*arc).clone())
+    }
+
+    fn data(&self) -> &ArcData<T> {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> Deref for ArcMake<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data().data
+    }
+}
+
+impl<T> Clone for ArcMake<T> {
+    fn clone(&self) -> Self {
+        if self.data().ref_count.fetch_add(1, Relaxed) > usize::MAX / 2 {
+            std::process::abort();
+        }
+
+        ArcMake { ptr: self.ptr }
+    }
+}
+
+impl<T> Drop for ArcMake<T> {
+    fn drop(&mut self) {
+        if self.data().ref_count.fetch_sub(1, Release) == 1 {
+            fence(Acquire);
+            unsafe {
+                drop(Box::from_raw(self.ptr.as_ptr()));
+            }
+        }
+    }
+}
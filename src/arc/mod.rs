@@ -0,0 +1,14 @@
+//! Reference counted pointers (chapter 6).
+
+pub mod allocator;
+pub mod arc_make;
+pub mod atomic_arc;
+pub mod basic;
+pub mod rc_make;
+pub mod sharded;
+
+pub use allocator::{AllocError, Allocator, Global};
+pub use arc_make::{ArcMake, RefCountOverflow, StaticArcMake, WeakMake};
+pub use atomic_arc::{AtomicArc, AtomicOptionArc};
+pub use rc_make::{RcMake, WeakRc};
+pub use sharded::ShardedArc;
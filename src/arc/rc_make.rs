@@ -0,0 +1,132 @@
+//! Single-threaded twin of `ArcMake`/`WeakMake` with plain `Cell` counters.
+
+use std::cell::{Cell, UnsafeCell};
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::ptr::NonNull;
+
+struct RcData<T> {
+    // RcMake 개수
+    data_ref_count: Cell<usize>,
+    // RcMake + WeakRc 개수 (RcMake 도 각자 WeakRc 를 하나씩 가짐)
+    alloc_ref_count: Cell<usize>,
+    // 마지막 RcMake 가 drop 되면 그 자리에서 drop
+    data: UnsafeCell<ManuallyDrop<T>>,
+}
+
+/// Non-owning pointer to the data of an [`RcMake`].
+///
+/// `NonNull` makes it `!Send` and `!Sync`.
+pub struct WeakRc<T> {
+    ptr: NonNull<RcData<T>>,
+}
+
+/// Reference counted pointer for a single thread, without atomic operations.
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<atomic_and_locks::arc::RcMake<i32>>();
+/// ```
+pub struct RcMake<T> {
+    weak: WeakRc<T>,
+}
+
+// atomic 이 아니라 overflow 는 그냥 abort
+fn increment(count: &Cell<usize>) {
+    match count.get().checked_add(1) {
+        Some(n) => count.set(n),
+        None => std::process::abort(),
+    }
+}
+
+impl<T> RcMake<T> {
+    pub fn new(data: T) -> RcMake<T> {
+        RcMake {
+            weak: WeakRc {
+                ptr: NonNull::from(Box::leak(Box::new(RcData {
+                    data_ref_count: Cell::new(1),
+                    alloc_ref_count: Cell::new(1),
+                    data: UnsafeCell::new(ManuallyDrop::new(data)),
+                }))),
+            },
+        }
+    }
+
+    /// Returns a mutable reference if there are no other `RcMake`s or `WeakRc`s.
+    pub fn get_mut(rc: &mut Self) -> Option<&mut T> {
+        if rc.weak.data().alloc_ref_count.get() == 1 {
+            // 안전함: 다른 RcMake, WeakRc 없음
+            let rcdata = unsafe { rc.weak.ptr.as_mut() };
+            Some(rcdata.data.get_mut())
+        } else {
+            None
+        }
+    }
+
+    pub fn downgrade(rc: &Self) -> WeakRc<T> {
+        rc.weak.clone()
+    }
+}
+
+impl<T> WeakRc<T> {
+    fn data(&self) -> &RcData<T> {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Returns `None` once every `RcMake` has been dropped.
+    pub fn upgrade(&self) -> Option<RcMake<T>> {
+        let count = &self.data().data_ref_count;
+        if count.get() == 0 {
+            return None;
+        }
+        increment(count);
+        Some(RcMake { weak: self.clone() })
+    }
+}
+
+impl<T> Deref for RcMake<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // 안전함: RcMake 가 있으니 data 는 존재
+        unsafe { &*self.weak.data().data.get() }
+    }
+}
+
+impl<T> Clone for WeakRc<T> {
+    fn clone(&self) -> Self {
+        increment(&self.data().alloc_ref_count);
+        WeakRc { ptr: self.ptr }
+    }
+}
+
+impl<T> Clone for RcMake<T> {
+    fn clone(&self) -> Self {
+        let weak = self.weak.clone();
+        increment(&weak.data().data_ref_count);
+        RcMake { weak }
+    }
+}
+
+impl<T> Drop for WeakRc<T> {
+    fn drop(&mut self) {
+        let count = &self.data().alloc_ref_count;
+        count.set(count.get() - 1);
+        if count.get() == 0 {
+            unsafe {
+                drop(Box::from_raw(self.ptr.as_ptr()));
+            }
+        }
+    }
+}
+
+impl<T> Drop for RcMake<T> {
+    fn drop(&mut self) {
+        let count = &self.weak.data().data_ref_count;
+        count.set(count.get() - 1);
+        if count.get() == 0 {
+            // 안전함: 마지막 RcMake, 이제 data 접근 불가능
+            unsafe { ManuallyDrop::drop(&mut *self.weak.data().data.get()) }
+        }
+    }
+}
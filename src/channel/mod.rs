@@ -0,0 +1,4 @@
+//! Channels (chapter 5).
+
+pub mod mpsc;
+pub mod oneshot;
@@ -0,0 +1,342 @@
+//! Multi-message channel built on a `Mutex` and a `Condvar` (p.120).
+//!
+//! [`Channel`] is used by reference and never disconnects. [`channel`] and
+//! [`bounded`] split it into [`Sender`] and [`Receiver`] handles that, like
+//! `std::sync::mpsc`, report when the other side is gone.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+pub struct Channel<T> {
+    state: Mutex<State<T>>,
+    item_ready: Condvar,
+    // bounded 일 때만 사용: send 가 자리 날 때까지 대기
+    space_available: Condvar,
+    capacity: Option<usize>,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    // Channel 을 직접 쓰면 둘 다 1 로 고정
+    senders: usize,
+    receivers: usize,
+}
+
+/// Why [`Channel::try_send`] gave the message back.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum TrySendError<T> {
+    /// The channel is at capacity.
+    Full(T),
+    /// Every [`Receiver`] has been dropped.
+    Disconnected(T),
+}
+
+impl<T> TrySendError<T> {
+    pub fn into_inner(self) -> T {
+        match self {
+            TrySendError::Full(message) | TrySendError::Disconnected(message) => message,
+        }
+    }
+}
+
+// T 가 Debug 가 아니어도 출력 가능하도록
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("Full(..)"),
+            TrySendError::Disconnected(_) => f.write_str("Disconnected(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("sending on a full channel"),
+            TrySendError::Disconnected(_) => f.write_str("sending on a closed channel"),
+        }
+    }
+}
+
+impl<T> Error for TrySendError<T> {}
+
+/// Returned by [`Sender::send`] with the message once every [`Receiver`]
+/// has been dropped.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("sending on a closed channel")
+    }
+}
+
+impl<T> Error for SendError<T> {}
+
+/// Returned by [`Receiver::recv`] once the channel is empty and every
+/// [`Sender`] has been dropped.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("receiving on a closed channel")
+    }
+}
+
+impl Error for RecvError {}
+
+/// Returned by [`Receiver::recv_timeout`] and [`Receiver::recv_deadline`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RecvTimeoutError {
+    /// No message arrived in time.
+    Timeout,
+    /// The channel is empty and every [`Sender`] has been dropped.
+    Disconnected,
+}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => f.write_str("timed out waiting on channel"),
+            RecvTimeoutError::Disconnected => f.write_str("receiving on a closed channel"),
+        }
+    }
+}
+
+impl Error for RecvTimeoutError {}
+
+impl From<RecvError> for RecvTimeoutError {
+    fn from(_: RecvError) -> Self {
+        RecvTimeoutError::Disconnected
+    }
+}
+
+impl<T> Channel<T> {
+    /// Creates a channel without a capacity limit.
+    pub fn new() -> Self {
+        Self::with_capacity(None)
+    }
+
+    /// Creates a channel holding at most `capacity` messages; `send`
+    /// blocks while it is full.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be at least 1");
+        Self::with_capacity(Some(capacity))
+    }
+
+    fn with_capacity(capacity: Option<usize>) -> Self {
+        Self {
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                senders: 1,
+                receivers: 1,
+            }),
+            item_ready: Condvar::new(),
+            space_available: Condvar::new(),
+            capacity,
+        }
+    }
+
+    /// `None` for a channel from [`Channel::new`].
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    fn is_full(&self, queue: &VecDeque<T>) -> bool {
+        self.capacity.is_some_and(|c| queue.len() >= c)
+    }
+
+    /// Blocks while a bounded channel is full.
+    pub fn send(&self, message: T) {
+        // 직접 쓰는 Channel 은 receiver 가 사라지지 않음
+        let _ = self.send_inner(message);
+    }
+
+    /// Sends without blocking, giving the message back if the channel is full.
+    pub fn try_send(&self, message: T) -> Result<(), TrySendError<T>> {
+        let mut b = self.state.lock().unwrap();
+        if b.receivers == 0 {
+            return Err(TrySendError::Disconnected(message));
+        }
+        if self.is_full(&b.queue) {
+            return Err(TrySendError::Full(message));
+        }
+        b.queue.push_back(message);
+        drop(b);
+        self.item_ready.notify_one();
+        Ok(())
+    }
+
+    /// Blocks until a message is available.
+    pub fn receive(&self) -> T {
+        match self.receive_inner(None) {
+            Ok(message) => message,
+            Err(_) => unreachable!("Channel has no senders to drop"),
+        }
+    }
+
+    fn send_inner(&self, message: T) -> Result<(), SendError<T>> {
+        let mut b = self.state.lock().unwrap();
+        while b.receivers != 0 && self.is_full(&b.queue) {
+            b = self.space_available.wait(b).unwrap();
+        }
+        if b.receivers == 0 {
+            return Err(SendError(message));
+        }
+        b.queue.push_back(message);
+        drop(b);
+        self.item_ready.notify_one();
+        Ok(())
+    }
+
+    // deadline 이 None 이면 Timeout 을 돌려주지 않음
+    fn receive_inner(&self, deadline: Option<Instant>) -> Result<T, RecvTimeoutError> {
+        let mut b = self.state.lock().unwrap();
+        loop {
+            if let Some(message) = b.queue.pop_front() {
+                drop(b);
+                self.space_available.notify_one();
+                return Ok(message);
+            }
+            // 남은 message 를 다 받은 뒤에야 끊김을 알림
+            if b.senders == 0 {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+            b = match deadline {
+                None => self.item_ready.wait(b).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(RecvTimeoutError::Timeout);
+                    }
+                    // 가짜 wakeup 이면 남은 시간만큼 다시 기다림
+                    self.item_ready.wait_timeout(b, deadline - now).unwrap().0
+                }
+            };
+        }
+    }
+}
+
+impl<T> Default for Channel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sending half of [`channel`] or [`bounded`]; cloneable.
+pub struct Sender<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Blocks while a bounded channel is full. Fails, giving the message
+    /// back, once every [`Receiver`] has been dropped.
+    pub fn send(&self, message: T) -> Result<(), SendError<T>> {
+        self.channel.send_inner(message)
+    }
+
+    pub fn try_send(&self, message: T) -> Result<(), TrySendError<T>> {
+        self.channel.try_send(message)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.channel.state.lock().unwrap().senders += 1;
+        Self {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut b = self.channel.state.lock().unwrap();
+        b.senders -= 1;
+        if b.senders == 0 {
+            drop(b);
+            // 기다리는 receiver 를 모두 깨워 RecvError 를 돌려줌
+            self.channel.item_ready.notify_all();
+        }
+    }
+}
+
+/// Receiving half of [`channel`] or [`bounded`]; cloneable, each message
+/// goes to one receiver.
+pub struct Receiver<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Blocks until a message is available. Fails once the channel is
+    /// empty and every [`Sender`] has been dropped.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.channel.receive_inner(None).map_err(|_| RecvError)
+    }
+
+    /// Like [`recv`](Receiver::recv), but gives up after `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        // 너무 긴 timeout 은 기한 없이 기다림
+        self.channel
+            .receive_inner(Instant::now().checked_add(timeout))
+    }
+
+    /// Like [`recv`](Receiver::recv), but gives up at `deadline`.
+    pub fn recv_deadline(&self, deadline: Instant) -> Result<T, RecvTimeoutError> {
+        self.channel.receive_inner(Some(deadline))
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.channel.state.lock().unwrap().receivers += 1;
+        Self {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut b = self.channel.state.lock().unwrap();
+        b.receivers -= 1;
+        if b.receivers == 0 {
+            drop(b);
+            // 가득 차서 기다리는 sender 를 모두 깨워 SendError 를 돌려줌
+            self.channel.space_available.notify_all();
+        }
+    }
+}
+
+fn split<T>(channel: Channel<T>) -> (Sender<T>, Receiver<T>) {
+    let a = Arc::new(channel);
+    (Sender { channel: a.clone() }, Receiver { channel: a })
+}
+
+/// Unbounded channel with split handles.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    split(Channel::new())
+}
+
+/// Channel holding at most `capacity` messages, with split handles.
+///
+/// # Panics
+///
+/// If `capacity` is 0.
+pub fn bounded<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    split(Channel::bounded(capacity))
+}
@@ -1,8 +1,12 @@
+//! One-shot channel whose whole state lives in a single `AtomicU8` (p.129).
+
 use std::cell::UnsafeCell;
 use std::mem::MaybeUninit;
 use std::sync::atomic::AtomicU8;
 use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
 
+use super::TryRecvError;
+
 const EMPTY: u8 = 0;
 const WRITING: u8 = 1;
 const READY: u8 = 2;
@@ -24,16 +28,24 @@ impl<T> Channel<T> {
     }
 
     pub fn send(&self, message: T) {
+        if self.try_send(message).is_err() {
+            panic!("cant send more than one message!");
+        }
+    }
+
+    /// Gives the message back if one was already sent.
+    pub fn try_send(&self, message: T) -> Result<(), T> {
         if self
             .state
             .compare_exchange(EMPTY, WRITING, Relaxed, Relaxed)
             .is_err()
         {
-            panic!("cant send more than one message!");
+            return Err(message);
         }
 
         unsafe { (*self.message.get()).write(message) };
         self.state.store(READY, Release);
+        Ok(())
     }
 
     pub fn is_ready(&self) -> bool {
@@ -41,15 +53,30 @@ impl<T> Channel<T> {
     }
 
     pub fn receive(&self) -> T {
-        if self
+        match self.try_receive() {
+            Ok(message) => message,
+            Err(_) => panic!("no message available!"),
+        }
+    }
+
+    /// Never returns [`TryRecvError::SenderDropped`]: this channel has no
+    /// sender handle.
+    pub fn try_receive(&self) -> Result<T, TryRecvError> {
+        match self
             .state
             .compare_exchange(READY, READING, Acquire, Relaxed)
-            .is_err()
         {
-            panic!("no message available!");
+            Ok(_) => Ok(unsafe { (*self.message.get()).assume_init_read() }),
+            // READING 은 한 번 들어가면 그대로 남음
+            Err(READING) => Err(TryRecvError::AlreadyTaken),
+            Err(_) => Err(TryRecvError::Empty),
         }
+    }
+}
 
-        unsafe { (*self.message.get()).assume_init_read() }
+impl<T> Default for Channel<T> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -59,4 +86,4 @@ impl<T> Drop for Channel<T> {
             unsafe { self.message.get_mut().assume_init_drop() }
         }
     }
-}
\ No newline at end of file
+}
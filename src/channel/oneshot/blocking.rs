@@ -0,0 +1,190 @@
+//! Borrowed one-shot channel whose receiver parks until the message arrives (p.139).
+
+use std::cell::UnsafeCell;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use std::thread;
+use std::thread::Thread;
+use std::time::{Duration, Instant};
+
+use super::Canceled;
+
+const EMPTY: u8 = 0;
+const READY: u8 = 1;
+const TAKEN: u8 = 2;
+// Sender 가 보내지 않고 drop 됨
+const CLOSED: u8 = 3;
+
+pub struct Channel<T> {
+    message: UnsafeCell<MaybeUninit<T>>,
+    state: AtomicU8,
+}
+
+unsafe impl<T> Sync for Channel<T> where T: Send {}
+
+impl<T> Channel<T> {
+    pub const fn new() -> Self {
+        Self {
+            message: UnsafeCell::new(MaybeUninit::uninit()),
+            state: AtomicU8::new(EMPTY),
+        }
+    }
+
+    /// The calling thread becomes the receiving thread.
+    pub fn split<'a>(&'a mut self) -> (Sender<'a, T>, Receiver<'a, T>) {
+        *self = Self::new();
+        (
+            Sender {
+                channel: self,
+                receiving_thread: thread::current(),
+            },
+            Receiver {
+                channel: self,
+                _no_send: PhantomData,
+            },
+        )
+    }
+}
+
+impl<T> Default for Channel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Sender<'a, T> {
+    channel: &'a Channel<T>,
+    receiving_thread: Thread,
+}
+
+impl<T> Sender<'_, T> {
+    pub fn send(self, message: T) {
+        unsafe { (*self.channel.message.get()).write(message) };
+        self.channel.state.store(READY, Release);
+        self.receiving_thread.unpark();
+    }
+}
+
+impl<T> Drop for Sender<'_, T> {
+    fn drop(&mut self) {
+        // send 뒤의 drop 이면 이미 READY 라서 실패
+        if self
+            .channel
+            .state
+            .compare_exchange(EMPTY, CLOSED, Relaxed, Relaxed)
+            .is_ok()
+        {
+            self.receiving_thread.unpark();
+        }
+    }
+}
+
+pub struct Receiver<'a, T> {
+    channel: &'a Channel<T>,
+    // marker type
+    _no_send: PhantomData<*const ()>,
+}
+
+/// Returned by [`Receiver::recv_timeout`] and [`Receiver::recv_deadline`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RecvTimeoutError {
+    /// No message arrived in time.
+    Timeout,
+    /// The sender was dropped without sending.
+    Canceled,
+}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => f.write_str("timed out waiting on channel"),
+            RecvTimeoutError::Canceled => f.write_str("sender dropped without sending"),
+        }
+    }
+}
+
+impl Error for RecvTimeoutError {}
+
+impl From<Canceled> for RecvTimeoutError {
+    fn from(_: Canceled) -> Self {
+        RecvTimeoutError::Canceled
+    }
+}
+
+impl<T> Receiver<'_, T> {
+    /// Returns `Err(Canceled)` if the sender was dropped without sending.
+    ///
+    /// # Panics
+    ///
+    /// If the message was already received with `recv_timeout` or
+    /// `recv_deadline`.
+    pub fn receive(self) -> Result<T, Canceled> {
+        match self.wait(None) {
+            Ok(message) => Ok(message),
+            Err(RecvTimeoutError::Canceled) => Err(Canceled),
+            Err(RecvTimeoutError::Timeout) => unreachable!("no deadline to miss"),
+        }
+    }
+
+    /// Parks for at most `timeout`. After a timeout the message may still
+    /// arrive and can be waited for again.
+    ///
+    /// # Panics
+    ///
+    /// If the message was already received.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        // 너무 긴 timeout 은 기한 없이 기다림
+        self.wait(Instant::now().checked_add(timeout))
+    }
+
+    /// Parks until `deadline` at most. After a timeout the message may
+    /// still arrive and can be waited for again.
+    ///
+    /// # Panics
+    ///
+    /// If the message was already received.
+    pub fn recv_deadline(&self, deadline: Instant) -> Result<T, RecvTimeoutError> {
+        self.wait(Some(deadline))
+    }
+
+    fn wait(&self, deadline: Option<Instant>) -> Result<T, RecvTimeoutError> {
+        // park 는 unpark 없이도 깨어날 수 있으므로 매번 state 부터 확인
+        loop {
+            match self
+                .channel
+                .state
+                .compare_exchange(READY, TAKEN, Acquire, Relaxed)
+            {
+                Ok(_) => break,
+                Err(CLOSED) => return Err(RecvTimeoutError::Canceled),
+                // recv_timeout 으로 이미 받았으면 더 올 message 가 없음
+                Err(TAKEN) => panic!("no message available!"),
+                Err(_) => {}
+            }
+            match deadline {
+                None => thread::park(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(RecvTimeoutError::Timeout);
+                    }
+                    thread::park_timeout(deadline - now);
+                }
+            }
+        }
+        // 안전함: READY 를 TAKEN 으로 바꾼 쪽만 message 를 읽음
+        Ok(unsafe { (*self.channel.message.get()).assume_init_read() })
+    }
+}
+
+impl<T> Drop for Channel<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == READY {
+            unsafe { self.message.get_mut().assume_init_drop() }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! One-shot channel that borrows its storage instead of allocating (p.136).
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+
+use super::TryRecvError;
+
+const EMPTY: u8 = 0;
+const READY: u8 = 1;
+const TAKEN: u8 = 2;
+// Sender 가 보내지 않고 drop 됨
+const CLOSED: u8 = 3;
+
+pub struct Channel<T> {
+    message: UnsafeCell<MaybeUninit<T>>,
+    state: AtomicU8,
+}
+
+unsafe impl<T> Sync for Channel<T> where T: Send {}
+
+impl<T> Channel<T> {
+    pub const fn new() -> Self {
+        Self {
+            message: UnsafeCell::new(MaybeUninit::uninit()),
+            state: AtomicU8::new(EMPTY),
+        }
+    }
+
+    pub fn split<'a>(&'a mut self) -> (Sender<'a, T>, Receiver<'a, T>) {
+        *self = Self::new();
+        (Sender { channel: self }, Receiver { channel: self })
+    }
+}
+
+impl<T> Default for Channel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Sender<'a, T> {
+    channel: &'a Channel<T>,
+}
+
+impl<T> Sender<'_, T> {
+    pub fn send(self, message: T) {
+        unsafe { (*self.channel.message.get()).write(message) };
+        self.channel.state.store(READY, Release);
+    }
+}
+
+impl<T> Drop for Sender<'_, T> {
+    fn drop(&mut self) {
+        // send 뒤의 drop 이면 이미 READY 라서 실패
+        let _ = self
+            .channel
+            .state
+            .compare_exchange(EMPTY, CLOSED, Relaxed, Relaxed);
+    }
+}
+
+pub struct Receiver<'a, T> {
+    channel: &'a Channel<T>,
+}
+
+impl<T> Receiver<'_, T> {
+    pub fn is_ready(&self) -> bool {
+        self.channel.state.load(Relaxed) == READY
+    }
+
+    pub fn receive(self) -> T {
+        match self.try_receive() {
+            Ok(message) => message,
+            Err(_) => panic!("no message available!"),
+        }
+    }
+
+    pub fn try_receive(&self) -> Result<T, TryRecvError> {
+        match self
+            .channel
+            .state
+            .compare_exchange(READY, TAKEN, Acquire, Relaxed)
+        {
+            Ok(_) => Ok(unsafe { (*self.channel.message.get()).assume_init_read() }),
+            Err(TAKEN) => Err(TryRecvError::AlreadyTaken),
+            Err(CLOSED) => Err(TryRecvError::SenderDropped),
+            Err(_) => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+impl<T> Drop for Channel<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == READY {
+            unsafe { self.message.get_mut().assume_init_drop() }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! One-shot channel made safe with run-time checks (p.125).
+//!
+//! The book's version keeps two `AtomicBool`s: `in_use`, set by the sender
+//! before writing, and `ready`, set once the message is written. That is
+//! enough for `send` and `receive`, but not for an exact `try_receive`:
+//! `in_use` must stay set so a second send fails, so after the message is
+//! taken the flags look the same as while it is still being written, and
+//! `Empty` can't be told apart from `AlreadyTaken`.
+//!
+//! Those run-time checks need one state with four values, which is the
+//! channel of the next section, so this module uses
+//! [`atomic_state::Channel`](super::atomic_state::Channel) (p.129).
+
+pub use super::atomic_state::Channel;
@@ -0,0 +1,50 @@
+//! One-shot channels, from the unsafe version to the blocking one.
+//!
+//! The type-safe [`typed`] channel is re-exported at this level.
+
+use std::error::Error;
+use std::fmt;
+
+pub mod atomic_state;
+pub mod blocking;
+pub mod borrowed;
+pub mod checked;
+pub mod typed;
+pub mod unchecked;
+
+pub use typed::{channel, Receiver, Sender};
+
+/// Why a `try_receive` returned no message.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TryRecvError {
+    /// Nothing has been sent yet.
+    Empty,
+    /// The message has already been received.
+    AlreadyTaken,
+    /// The sender was dropped without sending; nothing will arrive.
+    SenderDropped,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => f.write_str("no message available"),
+            TryRecvError::AlreadyTaken => f.write_str("message already received"),
+            TryRecvError::SenderDropped => f.write_str("sender dropped without sending"),
+        }
+    }
+}
+
+impl Error for TryRecvError {}
+
+/// Returned by `receive` when the sender was dropped without sending.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Canceled;
+
+impl fmt::Display for Canceled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("sender dropped without sending")
+    }
+}
+
+impl Error for Canceled {}
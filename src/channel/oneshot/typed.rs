@@ -0,0 +1,93 @@
+//! One-shot channel made safe through the type system (p.132).
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use std::sync::Arc;
+
+use super::{Canceled, TryRecvError};
+
+const EMPTY: u8 = 0;
+const READY: u8 = 1;
+const TAKEN: u8 = 2;
+// Sender 가 보내지 않고 drop 됨
+const CLOSED: u8 = 3;
+
+pub struct Sender<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Sender<T> {
+    pub fn send(self, message: T) {
+        unsafe { (*self.channel.message.get()).write(message) };
+        self.channel.state.store(READY, Release);
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        // send 뒤의 drop 이면 이미 READY 라서 실패
+        let _ = self
+            .channel
+            .state
+            .compare_exchange(EMPTY, CLOSED, Relaxed, Relaxed);
+    }
+}
+
+pub struct Receiver<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// True once `receive` will not panic: the message arrived or the
+    /// sender was dropped.
+    pub fn is_ready(&self) -> bool {
+        matches!(self.channel.state.load(Relaxed), READY | CLOSED)
+    }
+
+    /// Returns `Err(Canceled)` if the sender was dropped without sending.
+    pub fn receive(self) -> Result<T, Canceled> {
+        match self.try_receive() {
+            Ok(message) => Ok(message),
+            Err(TryRecvError::SenderDropped) => Err(Canceled),
+            Err(_) => panic!("no message available!"),
+        }
+    }
+
+    pub fn try_receive(&self) -> Result<T, TryRecvError> {
+        match self
+            .channel
+            .state
+            .compare_exchange(READY, TAKEN, Acquire, Relaxed)
+        {
+            Ok(_) => Ok(unsafe { (*self.channel.message.get()).assume_init_read() }),
+            Err(TAKEN) => Err(TryRecvError::AlreadyTaken),
+            Err(CLOSED) => Err(TryRecvError::SenderDropped),
+            Err(_) => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+struct Channel<T> {
+    message: UnsafeCell<MaybeUninit<T>>,
+    state: AtomicU8,
+}
+
+unsafe impl<T> Sync for Channel<T> where T: Send {}
+
+impl<T> Drop for Channel<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == READY {
+            unsafe { self.message.get_mut().assume_init_drop() }
+        }
+    }
+}
+
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let a = Arc::new(Channel {
+        message: UnsafeCell::new(MaybeUninit::uninit()),
+        state: AtomicU8::new(EMPTY),
+    });
+    (Sender { channel: a.clone() }, Receiver { channel: a })
+}
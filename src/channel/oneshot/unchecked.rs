@@ -1,14 +1,16 @@
+//! One-shot channel that leaves every check to the caller (p.122).
+
 use std::cell::UnsafeCell;
 use std::mem::MaybeUninit;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::{Acquire, Release};
 
 pub struct Channel<T> {
     message: UnsafeCell<MaybeUninit<T>>,
     ready: AtomicBool,
 }
 
-unsafe impl<T> Sync for Channel<T>
-where T: Send {}
+unsafe impl<T> Sync for Channel<T> where T: Send {}
 
 impl<T> Channel<T> {
     pub const fn new() -> Self {
@@ -18,6 +20,9 @@ impl<T> Channel<T> {
         }
     }
 
+    /// # Safety
+    ///
+    /// Must be called at most once.
     pub unsafe fn send(&self, message: T) {
         (*self.message.get()).write(message);
         self.ready.store(true, Release);
@@ -27,8 +32,17 @@ impl<T> Channel<T> {
         self.ready.load(Acquire)
     }
 
+    /// # Safety
+    ///
+    /// Must be called only once, and only after `is_ready()` returned `true`.
     // 안전함: is_ready() 가 true 리턴한 다음
     pub unsafe fn receive(&self) -> T {
         (*self.message.get()).assume_init_read()
     }
-}
\ No newline at end of file
+}
+
+impl<T> Default for Channel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
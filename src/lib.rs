@@ -0,0 +1,9 @@
+//! Building blocks from "Rust Atomics and Locks", packaged as a library.
+//!
+//! Each chapter's example binary lives under `examples/` and uses the
+//! types exported here instead of carrying its own copy.
+
+pub mod arc;
+pub mod channel;
+pub mod memory_ordering;
+pub mod reclaim;
@@ -0,0 +1,107 @@
+//! Acquire/release helpers (chapter 3).
+
+use std::cell::UnsafeCell;
+use std::marker::PhantomData;
+use std::ptr;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use std::sync::atomic::{AtomicBool, AtomicPtr};
+
+/// Lazily initialized heap value shared through an `AtomicPtr` (p.93).
+///
+/// Racing initializers all build a value, one of them wins the
+/// `compare_exchange` and the others drop theirs.
+pub struct LazyBox<T> {
+    ptr: AtomicPtr<T>,
+    _owned: PhantomData<T>,
+}
+
+unsafe impl<T: Send + Sync> Sync for LazyBox<T> {}
+
+impl<T> LazyBox<T> {
+    pub const fn new() -> Self {
+        Self {
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            _owned: PhantomData,
+        }
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        // 안전함: null 이 아니면 get_or_init 이 Release 로 게시한 값
+        unsafe { self.ptr.load(Acquire).as_ref() }
+    }
+
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        let mut p = self.ptr.load(Acquire);
+        if p.is_null() {
+            p = Box::into_raw(Box::new(f()));
+            if let Err(e) = self
+                .ptr
+                .compare_exchange(ptr::null_mut(), p, Release, Acquire)
+            {
+                // 다른 스레드가 먼저 초기화함
+                drop(unsafe { Box::from_raw(p) });
+                p = e;
+            }
+        }
+        unsafe { &*p }
+    }
+}
+
+impl<T> Default for LazyBox<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LazyBox<T> {
+    fn drop(&mut self) {
+        let p = *self.ptr.get_mut();
+        if !p.is_null() {
+            drop(unsafe { Box::from_raw(p) });
+        }
+    }
+}
+
+/// Lock that never blocks: `try_with` either runs or gives up (p.90).
+pub struct TryLock<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Sync for TryLock<T> {}
+
+impl<T> TryLock<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Runs `f` with exclusive access, or returns `None` if the lock is held.
+    pub fn try_with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        if self
+            .locked
+            .compare_exchange(false, true, Acquire, Relaxed)
+            .is_err()
+        {
+            return None;
+        }
+        let _unlock = Unlock(&self.locked);
+        // 안전함: lock 을 잡고 있는 동안 독점적 접근
+        Some(f(unsafe { &mut *self.value.get() }))
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+// f 가 panic 해도 lock 해제
+struct Unlock<'a>(&'a AtomicBool);
+
+impl Drop for Unlock<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Release);
+    }
+}
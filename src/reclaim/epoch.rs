@@ -0,0 +1,565 @@
+//! Epoch-based reclamation.
+//!
+//! Threads read shared pointers only while pinned (holding a [`Guard`]).
+//! Objects unlinked from a structure are handed to [`Guard::defer_destroy`]
+//! and freed once the global epoch has advanced twice, which can only
+//! happen after every thread pinned at unlink time has unpinned.
+//!
+//! ```
+//! use atomic_and_locks::reclaim::epoch::{self, Atomic, Owned};
+//! use std::sync::atomic::Ordering::SeqCst;
+//!
+//! let a = Atomic::new(1);
+//! let guard = epoch::pin();
+//! let old = a.swap(Owned::new(2), SeqCst, &guard);
+//! assert_eq!(unsafe { *old.deref() }, 1);
+//! // 안전함: old 는 a 에서 빠졌고 다른 곳에서 destroy 하지 않음
+//! unsafe { guard.defer_destroy(old) };
+//! # drop(guard);
+//! # unsafe { drop(a.into_owned()) };
+//! ```
+
+use std::cell::{Cell, UnsafeCell};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::sync::atomic::Ordering::{self, Acquire, Relaxed, Release, SeqCst};
+use std::sync::atomic::{fence, AtomicPtr, AtomicUsize};
+use std::sync::{Arc, Mutex, OnceLock};
+
+// epoch 는 2 씩 증가, 최하위 bit 는 pin 여부
+const PINNED: usize = 1;
+// local bag 이 이만큼 차면 global 로 넘기고 회수 시도
+const BAG_SIZE: usize = 64;
+// pin 이 이만큼 쌓일 때마다 회수 시도
+const PINS_PER_COLLECT: usize = 128;
+
+/// Deferred function, run once no thread can still see what it frees.
+struct Deferred(Box<dyn FnOnce()>);
+
+// 안전함: defer_unchecked 의 caller 가 다른 스레드에서 실행해도 된다고 보장
+unsafe impl Send for Deferred {}
+
+struct SealedBag {
+    // bag 을 넘길 때의 global epoch: 안의 객체는 모두 이 epoch 이전에 unlink 됨
+    epoch: usize,
+    items: Vec<Deferred>,
+}
+
+struct LocalPtr(*const Local);
+
+// 안전함: 다른 스레드는 Local::epoch (atomic) 만 읽음
+unsafe impl Send for LocalPtr {}
+
+struct GlobalData {
+    epoch: AtomicUsize,
+    locals: Mutex<Vec<LocalPtr>>,
+    garbage: Mutex<Vec<SealedBag>>,
+}
+
+impl GlobalData {
+    /// Advances the epoch if every pinned thread has seen the current one.
+    fn try_advance(&self) -> usize {
+        let epoch = self.epoch.load(Relaxed);
+        // pin 의 SeqCst fence 와 짝
+        fence(SeqCst);
+        // 목록에서 빠지기 전까지 Local 은 해제되지 않음
+        let locals = self.locals.lock().unwrap();
+        for local in locals.iter() {
+            let e = unsafe { (*local.0).epoch.load(Relaxed) };
+            if e & PINNED != 0 && e & !PINNED != epoch {
+                return epoch;
+            }
+        }
+        drop(locals);
+        fence(Acquire);
+        match self
+            .epoch
+            .compare_exchange(epoch, epoch.wrapping_add(2), Release, Relaxed)
+        {
+            Ok(_) => epoch.wrapping_add(2),
+            // 다른 스레드가 먼저 올림
+            Err(e) => e,
+        }
+    }
+
+    fn push_bag(&self, items: Vec<Deferred>) {
+        if items.is_empty() {
+            return;
+        }
+        // 앞선 unlink 가 epoch 읽기보다 먼저 보이도록: pin 의 SeqCst fence 와 짝
+        // 없으면 오래된 epoch 으로 봉인되어 아직 pin 된 reader 가 있는데 해제될 수 있음
+        fence(SeqCst);
+        let epoch = self.epoch.load(Relaxed);
+        self.garbage
+            .lock()
+            .unwrap()
+            .push(SealedBag { epoch, items });
+    }
+
+    /// Runs the deferred functions of bags sealed two epochs ago or earlier.
+    fn collect(&self) {
+        let epoch = self.try_advance();
+        let ready: Vec<SealedBag> = {
+            let mut garbage = self.garbage.lock().unwrap();
+            let (ready, keep) = mem::take(&mut *garbage)
+                .into_iter()
+                .partition(|bag| epoch.wrapping_sub(bag.epoch) >= 4);
+            *garbage = keep;
+            ready
+        };
+        // lock 밖에서 실행: drop 안에서 다시 defer 해도 됨
+        for bag in ready {
+            for deferred in bag.items {
+                (deferred.0)();
+            }
+        }
+    }
+}
+
+impl Drop for GlobalData {
+    fn drop(&mut self) {
+        // Collector 와 모든 Local 이 사라짐: pin 된 스레드 없음
+        for bag in self.garbage.get_mut().unwrap().drain(..) {
+            for deferred in bag.items {
+                (deferred.0)();
+            }
+        }
+    }
+}
+
+/// A thread's registration with a [`Collector`].
+struct Local {
+    // pin 된 epoch | PINNED, pin 되지 않았으면 0
+    epoch: AtomicUsize,
+    // 아래는 소유 스레드만 접근
+    guard_count: Cell<usize>,
+    handle_count: Cell<usize>,
+    pin_count: Cell<usize>,
+    bag: UnsafeCell<Vec<Deferred>>,
+    global: Arc<GlobalData>,
+}
+
+impl Local {
+    fn pin(&self) {
+        let guards = self.guard_count.get();
+        self.guard_count.set(guards + 1);
+        if guards != 0 {
+            // 이미 pin 됨
+            return;
+        }
+        let epoch = self.global.epoch.load(Relaxed);
+        self.epoch.store(epoch | PINNED, Relaxed);
+        // 이후의 load 가 try_advance 의 epoch 확인보다 먼저 보이지 않도록
+        fence(SeqCst);
+
+        let pins = self.pin_count.get().wrapping_add(1);
+        self.pin_count.set(pins);
+        if pins.is_multiple_of(PINS_PER_COLLECT) {
+            self.global.collect();
+        }
+    }
+
+    /// Returns whether the `Local` should now be finalized.
+    fn unpin(&self) -> bool {
+        let guards = self.guard_count.get() - 1;
+        self.guard_count.set(guards);
+        if guards != 0 {
+            return false;
+        }
+        self.epoch.store(0, Release);
+        self.handle_count.get() == 0
+    }
+
+    fn defer(&self, deferred: Deferred) {
+        // 안전함: bag 은 소유 스레드만 접근하고 아래 collect 전에 빌림이 끝남
+        let bag = unsafe { &mut *self.bag.get() };
+        bag.push(deferred);
+        if bag.len() >= BAG_SIZE {
+            self.flush();
+        }
+    }
+
+    fn flush(&self) {
+        let bag = unsafe { mem::take(&mut *self.bag.get()) };
+        self.global.push_bag(bag);
+        self.global.collect();
+    }
+
+    /// Unregisters and frees the `Local` once no handle or guard uses it.
+    unsafe fn finalize(local: *const Local) {
+        let bag = mem::take(&mut *(*local).bag.get());
+        let global = (*local).global.clone();
+        global.push_bag(bag);
+        global.locals.lock().unwrap().retain(|l| l.0 != local);
+        drop(Box::from_raw(local as *mut Local));
+    }
+}
+
+/// Owner of the global epoch and the garbage waiting to be freed.
+///
+/// Threads take part through a [`LocalHandle`] from [`Collector::register`].
+/// Garbage still pending when the collector and every handle are gone is
+/// freed at that point.
+#[derive(Clone)]
+pub struct Collector {
+    global: Arc<GlobalData>,
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        Self {
+            global: Arc::new(GlobalData {
+                epoch: AtomicUsize::new(0),
+                locals: Mutex::new(Vec::new()),
+                garbage: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Registers the current thread.
+    pub fn register(&self) -> LocalHandle {
+        let local = Box::into_raw(Box::new(Local {
+            epoch: AtomicUsize::new(0),
+            guard_count: Cell::new(0),
+            handle_count: Cell::new(1),
+            pin_count: Cell::new(0),
+            bag: UnsafeCell::new(Vec::new()),
+            global: self.global.clone(),
+        }));
+        self.global.locals.lock().unwrap().push(LocalPtr(local));
+        LocalHandle { local }
+    }
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A thread's registration with a [`Collector`], used to [`pin`](LocalHandle::pin).
+pub struct LocalHandle {
+    // raw pointer 라 !Send: 등록한 스레드에서만 사용
+    local: *const Local,
+}
+
+impl LocalHandle {
+    pub fn pin(&self) -> Guard {
+        unsafe { (*self.local).pin() };
+        Guard { local: self.local }
+    }
+}
+
+impl Drop for LocalHandle {
+    fn drop(&mut self) {
+        let local = unsafe { &*self.local };
+        local.handle_count.set(local.handle_count.get() - 1);
+        if local.handle_count.get() == 0 && local.guard_count.get() == 0 {
+            unsafe { Local::finalize(self.local) };
+        }
+    }
+}
+
+/// Keeps the current thread pinned; pointers loaded through it stay valid
+/// until it is dropped.
+pub struct Guard {
+    local: *const Local,
+}
+
+impl Guard {
+    /// Destroys the object behind `ptr` once no pinned thread can reach it.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been unlinked so that threads pinning from now on
+    /// cannot load it, must come from an [`Owned`], and must not be
+    /// destroyed again.
+    pub unsafe fn defer_destroy<T>(&self, ptr: Shared<'_, T>) {
+        let ptr = ptr.as_raw() as *mut T;
+        self.defer_unchecked(move || drop(Box::from_raw(ptr)));
+    }
+
+    /// Runs `f` once no pinned thread can reach what it touches.
+    pub fn defer<F: FnOnce() + Send + 'static>(&self, f: F) {
+        unsafe { self.defer_unchecked(f) }
+    }
+
+    /// Like [`Guard::defer`] without the `Send` and `'static` bounds.
+    ///
+    /// # Safety
+    ///
+    /// `f` may run on any thread and after the current scope has ended.
+    pub unsafe fn defer_unchecked<F: FnOnce()>(&self, f: F) {
+        let f: Box<dyn FnOnce() + '_> = Box::new(f);
+        // 안전함: caller 가 lifetime 과 스레드에 대해 보장
+        let f: Box<dyn FnOnce()> = mem::transmute(f);
+        (*self.local).defer(Deferred(f));
+    }
+
+    /// Hands this thread's deferred functions to the collector and runs
+    /// whatever has become safe to run.
+    pub fn flush(&self) {
+        unsafe { (*self.local).flush() };
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        // finalize 가 Local 을 해제하므로 &Local 이 남지 않게 따로 호출
+        if unsafe { (*self.local).unpin() } {
+            unsafe { Local::finalize(self.local) };
+        }
+    }
+}
+
+fn default_collector() -> &'static Collector {
+    static COLLECTOR: OnceLock<Collector> = OnceLock::new();
+    COLLECTOR.get_or_init(Collector::new)
+}
+
+thread_local! {
+    static HANDLE: LocalHandle = default_collector().register();
+}
+
+/// Pins the current thread with the process-wide default [`Collector`].
+pub fn pin() -> Guard {
+    HANDLE
+        .try_with(LocalHandle::pin)
+        // thread local 이 이미 정리된 경우: 임시 등록, Guard 가 Local 을 유지
+        .unwrap_or_else(|_| default_collector().register().pin())
+}
+
+/// [`Owned`] or [`Shared`]: what can be stored into an [`Atomic`].
+pub trait Pointer<T> {
+    fn into_ptr(self) -> *mut T;
+
+    /// # Safety
+    ///
+    /// `ptr` must come from `into_ptr` of the same pointer type.
+    unsafe fn from_ptr(ptr: *mut T) -> Self;
+}
+
+/// Atomic pointer to a heap object, read through a [`Guard`].
+///
+/// Dropping it does not free the object; see [`Atomic::into_owned`].
+pub struct Atomic<T> {
+    ptr: AtomicPtr<T>,
+    _marker: PhantomData<*mut T>,
+}
+
+unsafe impl<T: Send + Sync> Send for Atomic<T> {}
+unsafe impl<T: Send + Sync> Sync for Atomic<T> {}
+
+/// A failed [`Atomic::compare_exchange`]: the value found and the unused `new`.
+pub struct CompareExchangeError<'g, T, P: Pointer<T>> {
+    pub current: Shared<'g, T>,
+    pub new: P,
+}
+
+impl<T> Atomic<T> {
+    pub const fn null() -> Self {
+        Self {
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn new(value: T) -> Self {
+        Self::from(Owned::new(value))
+    }
+
+    pub fn load<'g>(&self, order: Ordering, _: &'g Guard) -> Shared<'g, T> {
+        unsafe { Shared::from_ptr(self.ptr.load(order)) }
+    }
+
+    pub fn store<P: Pointer<T>>(&self, new: P, order: Ordering) {
+        self.ptr.store(new.into_ptr(), order);
+    }
+
+    /// Stores `new` and returns the previous pointer.
+    pub fn swap<'g, P: Pointer<T>>(&self, new: P, order: Ordering, _: &'g Guard) -> Shared<'g, T> {
+        unsafe { Shared::from_ptr(self.ptr.swap(new.into_ptr(), order)) }
+    }
+
+    /// Stores `new` if the pointer is still `current`.
+    pub fn compare_exchange<'g, P: Pointer<T>>(
+        &self,
+        current: Shared<'_, T>,
+        new: P,
+        success: Ordering,
+        failure: Ordering,
+        _: &'g Guard,
+    ) -> Result<Shared<'g, T>, CompareExchangeError<'g, T, P>> {
+        let new = new.into_ptr();
+        match self
+            .ptr
+            .compare_exchange(current.as_raw() as *mut T, new, success, failure)
+        {
+            Ok(_) => Ok(unsafe { Shared::from_ptr(new) }),
+            Err(found) => Err(CompareExchangeError {
+                current: unsafe { Shared::from_ptr(found) },
+                // 안전함: 저장되지 않았으니 그대로 돌려줌
+                new: unsafe { P::from_ptr(new) },
+            }),
+        }
+    }
+
+    /// Takes ownership of the object, e.g. when tearing down a structure.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must not be null and no other thread may still use it.
+    pub unsafe fn into_owned(self) -> Owned<T> {
+        Owned::from_ptr(self.ptr.into_inner())
+    }
+}
+
+impl<T> Default for Atomic<T> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T> From<Owned<T>> for Atomic<T> {
+    fn from(owned: Owned<T>) -> Self {
+        Self {
+            ptr: AtomicPtr::new(owned.into_ptr()),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Uniquely owned heap object, not yet shared through an [`Atomic`].
+pub struct Owned<T> {
+    boxed: Box<T>,
+}
+
+impl<T> Owned<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            boxed: Box::new(value),
+        }
+    }
+
+    /// Gives up ownership; the object now has to be freed through a [`Guard`].
+    pub fn into_shared<'g>(self, _: &'g Guard) -> Shared<'g, T> {
+        unsafe { Shared::from_ptr(self.into_ptr()) }
+    }
+
+    pub fn into_box(self) -> Box<T> {
+        self.boxed
+    }
+}
+
+impl<T> Pointer<T> for Owned<T> {
+    fn into_ptr(self) -> *mut T {
+        Box::into_raw(self.boxed)
+    }
+
+    unsafe fn from_ptr(ptr: *mut T) -> Self {
+        Self {
+            boxed: Box::from_raw(ptr),
+        }
+    }
+}
+
+impl<T> Deref for Owned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.boxed
+    }
+}
+
+impl<T> DerefMut for Owned<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.boxed
+    }
+}
+
+/// Pointer loaded from an [`Atomic`], valid while the guard `'g` is alive.
+pub struct Shared<'g, T> {
+    ptr: *const T,
+    _marker: PhantomData<(&'g (), *const T)>,
+}
+
+impl<T> Clone for Shared<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Shared<'_, T> {}
+
+impl<T> PartialEq for Shared<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.ptr, other.ptr)
+    }
+}
+
+impl<T> Eq for Shared<'_, T> {}
+
+impl<T> fmt::Debug for Shared<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&self.ptr, f)
+    }
+}
+
+impl<'g, T> Shared<'g, T> {
+    pub fn null() -> Self {
+        Self {
+            ptr: ptr::null(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.ptr.is_null()
+    }
+
+    pub fn as_raw(&self) -> *const T {
+        self.ptr
+    }
+
+    /// # Safety
+    ///
+    /// The pointer must not be null, and the object must not have been
+    /// destroyed other than through [`Guard::defer_destroy`].
+    pub unsafe fn deref(&self) -> &'g T {
+        &*self.ptr
+    }
+
+    /// Like [`Shared::deref`], with `None` for null.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`Shared::deref`] for non-null pointers.
+    pub unsafe fn as_ref(&self) -> Option<&'g T> {
+        self.ptr.as_ref()
+    }
+
+    /// Takes ownership of the object.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must not be null and no other thread may still use it.
+    pub unsafe fn into_owned(self) -> Owned<T> {
+        Owned::from_ptr(self.ptr as *mut T)
+    }
+}
+
+impl<T> Pointer<T> for Shared<'_, T> {
+    fn into_ptr(self) -> *mut T {
+        self.ptr as *mut T
+    }
+
+    unsafe fn from_ptr(ptr: *mut T) -> Self {
+        Self {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! Hazard pointers.
+//!
+//! A reader announces the pointer it is about to dereference in a hazard
+//! slot; a writer that unlinked an object [`retire`](HazardDomain::retire)s
+//! it, and the object is deleted only once no slot holds its address.
+//!
+//! Protecting a lazily published value like `get_data()` (p.93), where the
+//! value may also be replaced later:
+//!
+//! ```
+//! use atomic_and_locks::reclaim::hazard::{HazardDomain, HazardPointer};
+//! use std::sync::atomic::{AtomicPtr, Ordering::SeqCst};
+//!
+//! static CONFIG: AtomicPtr<String> = AtomicPtr::new(std::ptr::null_mut());
+//!
+//! fn replace(value: String) {
+//!     let old = CONFIG.swap(Box::into_raw(Box::new(value)), SeqCst);
+//!     if !old.is_null() {
+//!         // 안전함: old 는 CONFIG 에서 빠졌고 Box 로 만든 포인터
+//!         unsafe { HazardDomain::global().retire(old, |p| drop(Box::from_raw(p))) };
+//!     }
+//! }
+//!
+//! replace(String::from("v1"));
+//! let mut hp = HazardPointer::new();
+//! // 안전함: CONFIG 의 값은 replace 에서 retire 로만 해제됨
+//! let config = unsafe { hp.protect(&CONFIG) }.unwrap();
+//! replace(String::from("v2"));
+//! // 교체됐어도 hp 가 보호하는 동안 v1 은 살아 있음
+//! assert_eq!(config, "v1");
+//! ```
+
+use std::collections::HashSet;
+use std::mem;
+use std::ptr;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release, SeqCst};
+use std::sync::atomic::{fence, AtomicBool, AtomicPtr, AtomicUsize};
+use std::sync::Mutex;
+
+/// Retired objects a domain lets pile up before scanning, at minimum.
+pub const DEFAULT_THRESHOLD: usize = 64;
+
+struct Slot {
+    // 보호 중인 주소, 없으면 null
+    ptr: AtomicPtr<()>,
+    // HazardPointer 가 사용 중인지
+    active: AtomicBool,
+    // slot 은 domain 이 drop 될 때까지 해제하지 않음: lock 없이 순회 가능
+    next: *const Slot,
+}
+
+struct Retired {
+    addr: *mut (),
+    deleter: unsafe fn(*mut ()),
+}
+
+// 안전함: retire 의 caller 가 다른 스레드에서 삭제해도 된다고 보장
+unsafe impl Send for Retired {}
+
+/// Set of hazard slots and the objects retired against them.
+///
+/// At most `max(threshold, 2 * slots)` retired objects wait before a scan,
+/// and a scan leaves at most one per slot, so memory stays bounded no
+/// matter how long a reader holds on to a pointer.
+pub struct HazardDomain {
+    slots: AtomicPtr<Slot>,
+    slot_count: AtomicUsize,
+    retired: Mutex<Vec<Retired>>,
+    retired_count: AtomicUsize,
+    threshold: usize,
+}
+
+impl HazardDomain {
+    pub const fn new() -> Self {
+        Self::with_threshold(DEFAULT_THRESHOLD)
+    }
+
+    /// A domain that scans once `threshold` objects (or twice the number of
+    /// slots, if larger) have been retired.
+    pub const fn with_threshold(threshold: usize) -> Self {
+        Self {
+            slots: AtomicPtr::new(ptr::null_mut()),
+            slot_count: AtomicUsize::new(0),
+            retired: Mutex::new(Vec::new()),
+            retired_count: AtomicUsize::new(0),
+            threshold,
+        }
+    }
+
+    /// The process-wide domain used by [`HazardPointer::new`].
+    pub fn global() -> &'static HazardDomain {
+        static GLOBAL: HazardDomain = HazardDomain::new();
+        &GLOBAL
+    }
+
+    fn acquire_slot(&self) -> &Slot {
+        let mut p = self.slots.load(Acquire);
+        while let Some(slot) = unsafe { p.as_ref() } {
+            if !slot.active.load(Relaxed)
+                && slot
+                    .active
+                    .compare_exchange(false, true, Acquire, Relaxed)
+                    .is_ok()
+            {
+                return slot;
+            }
+            p = slot.next as *mut Slot;
+        }
+        // 빈 slot 이 없음: 새로 만들어 앞에 추가
+        let slot = Box::into_raw(Box::new(Slot {
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            active: AtomicBool::new(true),
+            next: ptr::null(),
+        }));
+        let mut head = self.slots.load(Relaxed);
+        loop {
+            unsafe { (*slot).next = head };
+            match self
+                .slots
+                .compare_exchange_weak(head, slot, Release, Relaxed)
+            {
+                Ok(_) => break,
+                Err(h) => head = h,
+            }
+        }
+        self.slot_count.fetch_add(1, Relaxed);
+        unsafe { &*slot }
+    }
+
+    /// Hands `ptr` to the domain, which calls `deleter(ptr)` once no
+    /// [`HazardPointer`] protects it.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must already be unlinked, so that a `protect` starting now can
+    /// no longer load it, must not be retired twice, and `deleter` must be
+    /// safe to call on it from any thread.
+    pub unsafe fn retire<T>(&self, ptr: *mut T, deleter: unsafe fn(*mut T)) {
+        let retired = Retired {
+            addr: ptr.cast(),
+            // 안전함: 같은 metadata 의 포인터끼리는 fn pointer ABI 가 같음
+            deleter: mem::transmute::<unsafe fn(*mut T), unsafe fn(*mut ())>(deleter),
+        };
+        let count = {
+            let mut list = self.retired.lock().unwrap();
+            list.push(retired);
+            // lock 안에서 세야 reclaim 이 가져간 것보다 먼저 빼지 않음
+            self.retired_count.fetch_add(1, Relaxed) + 1
+        };
+        let threshold = self.threshold.max(2 * self.slot_count.load(Relaxed));
+        if count >= threshold {
+            self.reclaim();
+        }
+    }
+
+    /// Number of retired objects not deleted yet.
+    pub fn pending(&self) -> usize {
+        self.retired_count.load(Relaxed)
+    }
+
+    /// Deletes every retired object no hazard pointer protects, and returns
+    /// how many were deleted.
+    pub fn reclaim(&self) -> usize {
+        let retired = mem::take(&mut *self.retired.lock().unwrap());
+        if retired.is_empty() {
+            return 0;
+        }
+        // protect 의 fence 와 짝: 보호를 못 봤다면 protect 가 unlink 를 봄
+        fence(SeqCst);
+        let mut hazards = HashSet::new();
+        let mut p = self.slots.load(Acquire);
+        while let Some(slot) = unsafe { p.as_ref() } {
+            // Acquire: reset_protection 전의 읽기가 삭제보다 먼저
+            let hazard = slot.ptr.load(Acquire);
+            if !hazard.is_null() {
+                hazards.insert(hazard);
+            }
+            p = slot.next as *mut Slot;
+        }
+
+        let (keep, delete): (Vec<_>, Vec<_>) =
+            retired.into_iter().partition(|r| hazards.contains(&r.addr));
+        let deleted = delete.len();
+        self.retired_count.fetch_sub(deleted, Relaxed);
+        if !keep.is_empty() {
+            self.retired.lock().unwrap().extend(keep);
+        }
+        // lock 밖에서 삭제: deleter 가 다시 retire 해도 됨
+        for r in delete {
+            unsafe { (r.deleter)(r.addr) };
+        }
+        deleted
+    }
+}
+
+impl Default for HazardDomain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for HazardDomain {
+    fn drop(&mut self) {
+        // &mut self: HazardPointer 가 남아 있을 수 없음
+        for r in self.retired.get_mut().unwrap().drain(..) {
+            unsafe { (r.deleter)(r.addr) };
+        }
+        let mut p = *self.slots.get_mut();
+        while !p.is_null() {
+            let slot = unsafe { Box::from_raw(p) };
+            p = slot.next as *mut Slot;
+        }
+    }
+}
+
+/// One hazard slot, protecting at most one pointer at a time.
+pub struct HazardPointer<'d> {
+    slot: &'d Slot,
+}
+
+impl HazardPointer<'static> {
+    /// Takes a slot in [`HazardDomain::global`].
+    pub fn new() -> Self {
+        Self::new_in(HazardDomain::global())
+    }
+}
+
+impl Default for HazardPointer<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'d> HazardPointer<'d> {
+    pub fn new_in(domain: &'d HazardDomain) -> Self {
+        Self {
+            slot: domain.acquire_slot(),
+        }
+    }
+
+    /// Loads `src` and protects the object it points to until the next
+    /// `protect` or [`reset_protection`](HazardPointer::reset_protection).
+    ///
+    /// Returns `None` for a null pointer.
+    ///
+    /// # Safety
+    ///
+    /// Every non-null pointer stored in `src` must point to a valid `T`
+    /// that is only freed through [`HazardDomain::retire`] on the domain of
+    /// this hazard pointer, after being unlinked from `src`.
+    pub unsafe fn protect<T>(&mut self, src: &AtomicPtr<T>) -> Option<&T> {
+        let mut p = src.load(Relaxed);
+        loop {
+            self.slot.ptr.store(p.cast(), Relaxed);
+            // reclaim 의 fence 와 짝: reclaim 이 이 보호를 보거나
+            // 아래 load 가 unlink 를 봄
+            fence(SeqCst);
+            let q = src.load(Acquire);
+            if p == q {
+                // 안전함: 보호가 보이는 동안 retire 된 p 는 삭제되지 않음
+                return p.as_ref();
+            }
+            p = q;
+        }
+    }
+
+    pub fn reset_protection(&mut self) {
+        self.slot.ptr.store(ptr::null_mut(), Release);
+    }
+}
+
+impl Drop for HazardPointer<'_> {
+    fn drop(&mut self) {
+        self.reset_protection();
+        self.slot.active.store(false, Release);
+    }
+}
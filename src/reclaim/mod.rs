@@ -0,0 +1,5 @@
+//! Memory reclamation for lock-free structures, as an alternative to the
+//! reference counting of [`crate::arc`].
+
+pub mod epoch;
+pub mod hazard;
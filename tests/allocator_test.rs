@@ -0,0 +1,89 @@
+use atomic_and_locks::arc::{AllocError, Allocator, ArcMake, Global};
+use atomic_and_locks::unsize_arc_make;
+use std::alloc::Layout;
+use std::collections::HashMap;
+use std::ptr::NonNull;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+use std::sync::Mutex;
+
+#[test]
+fn allocator() {
+    // 살아있는 블록 수를 세고, 해제할 때 할당 때와 같은 layout 인지 확인하는 allocator
+    #[derive(Default)]
+    struct Counting {
+        live: AtomicUsize,
+        total: AtomicUsize,
+        layouts: Mutex<HashMap<usize, Layout>>,
+    }
+
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+            self.live.fetch_add(1, Relaxed);
+            self.total.fetch_add(1, Relaxed);
+            let ptr = Global.allocate(layout)?;
+            self.layouts
+                .lock()
+                .unwrap()
+                .insert(ptr.addr().get(), layout);
+            Ok(ptr)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.live.fetch_sub(1, Relaxed);
+            let allocated = self.layouts.lock().unwrap().remove(&ptr.addr().get());
+            assert_eq!(allocated, Some(layout));
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    let counting = Counting::default();
+    let a = ArcMake::new_in(String::from("arena"), &counting);
+    let b = a.clone();
+    let w = ArcMake::downgrade(&a);
+    assert_eq!(counting.live.load(Relaxed), 1);
+    assert!(std::ptr::eq(*ArcMake::allocator(&a), &counting));
+
+    drop(a);
+    drop(b);
+    // WeakMake 가 남아 있으면 메모리는 아직 반환되지 않음
+    assert!(w.upgrade().is_none());
+    assert_eq!(counting.live.load(Relaxed), 1);
+    drop(w);
+    assert_eq!(counting.live.load(Relaxed), 0);
+
+    // make_mut 의 새 allocation 도 같은 allocator 에서
+    let mut a = ArcMake::new_in(vec![1], &counting);
+    let b = a.clone();
+    ArcMake::make_mut(&mut a).push(2);
+    assert_eq!((&*a, &*b), (&vec![1, 2], &vec![1]));
+    assert_eq!(counting.live.load(Relaxed), 2);
+    assert_eq!(ArcMake::try_unwrap(b).ok(), Some(vec![1]));
+    drop(a);
+    assert_eq!(counting.live.load(Relaxed), 0);
+    assert_eq!(counting.total.load(Relaxed), 3);
+
+    // 다른 스레드에서 drop 돼도 같은 allocator 로
+    let a = ArcMake::new_in(5, &counting);
+    std::thread::scope(|s| {
+        let b = a.clone();
+        s.spawn(move || assert_eq!(*b, 5));
+    });
+    drop(a);
+    assert_eq!(counting.live.load(Relaxed), 0);
+
+    // data 가 없어진 뒤 WeakMake 가 해제: 작은 data, 큰 data, dyn, 옮겨진 data
+    let a = ArcMake::new_in(1u8, &counting);
+    let w = ArcMake::downgrade(&a);
+    drop(a);
+    drop(w);
+    let a = unsize_arc_make!(ArcMake::new_in([7u64; 4], &counting) => dyn std::fmt::Debug);
+    let w = ArcMake::downgrade(&a);
+    drop(a);
+    drop(w);
+    let a = ArcMake::new_in(String::from("moved"), &counting);
+    let w = ArcMake::downgrade(&a);
+    assert_eq!(ArcMake::into_inner(a).as_deref(), Some("moved"));
+    drop(w);
+    assert_eq!(counting.live.load(Relaxed), 0);
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use atomic_and_locks::arc::{ArcMake, AtomicArc};
+use common::DetectDrop;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+
+#[test]
+fn atomic_arc() {
+    static NUM_DROPS: AtomicUsize = AtomicUsize::new(0);
+    let config = |n: usize| DetectDrop::new(n, &NUM_DROPS);
+
+    let first = ArcMake::new(config(0));
+    let slot = AtomicArc::new(first.clone());
+    let stale = first.clone();
+    assert!(ArcMake::ptr_eq(&slot.load(), &first));
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..10_000 {
+                    let c = slot.load();
+                    assert!(c.value <= 400);
+                }
+            });
+        }
+        for t in 0..4 {
+            let slot = &slot;
+            s.spawn(move || {
+                for i in 1..=100 {
+                    slot.store(ArcMake::new(config(t * 100 + i)));
+                }
+            });
+        }
+    });
+
+    // 이미 바뀐 값 기준 compare_and_swap 은 실패
+    let back = slot.compare_and_swap(&stale, ArcMake::new(config(999)));
+    assert!(back.is_err());
+    drop(back);
+    let current = slot.load();
+    let old = slot.compare_and_swap(&current, first).ok().unwrap();
+    assert!(ArcMake::ptr_eq(&old, &current));
+    drop((old, current, stale));
+
+    // config(0) 은 아직 slot 에 있음
+    assert_eq!(NUM_DROPS.load(Relaxed), 401);
+    drop(slot.into_inner());
+    assert_eq!(NUM_DROPS.load(Relaxed), 402);
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use atomic_and_locks::arc::{ArcMake, AtomicOptionArc};
+use common::DetectDrop;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+
+#[test]
+fn atomic_option_arc() {
+    static NUM_DROPS: AtomicUsize = AtomicUsize::new(0);
+    let data = || DetectDrop::new((), &NUM_DROPS);
+
+    static CELL: AtomicOptionArc<DetectDrop> = AtomicOptionArc::new();
+    assert!(CELL.load().is_none());
+
+    // 여러 스레드가 동시에 초기화: 하나만 남고 나머지는 drop
+    let barrier = std::sync::Barrier::new(8);
+    let winners: Vec<ArcMake<DetectDrop>> = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                s.spawn(|| {
+                    barrier.wait();
+                    CELL.get_or_init(data)
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    assert!(winners.iter().all(|w| ArcMake::ptr_eq(w, &winners[0])));
+    let created = NUM_DROPS.load(Relaxed) + 1;
+    assert!(created <= 8);
+
+    let taken = CELL.take().unwrap();
+    assert!(ArcMake::ptr_eq(&taken, &winners[0]));
+    assert!(CELL.load().is_none());
+    drop(winners);
+    drop(taken);
+    assert_eq!(NUM_DROPS.load(Relaxed), created);
+
+    assert!(CELL.replace(Some(ArcMake::new(data()))).is_none());
+    let old = CELL.replace(None).unwrap();
+    drop(old);
+    assert_eq!(NUM_DROPS.load(Relaxed), created + 1);
+
+    let cell = AtomicOptionArc::from(Some(ArcMake::new(data())));
+    assert!(cell.into_inner().is_some());
+    assert_eq!(NUM_DROPS.load(Relaxed), created + 2);
+}
@@ -0,0 +1,39 @@
+//! Helpers shared by the integration tests (`mod common;`).
+
+use std::fmt;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+
+/// Adds one to `drops` when dropped.
+///
+/// Each test passes its own `static` counter, so tests running in parallel
+/// in the same binary don't see each other's drops.
+pub struct DetectDrop<T = ()> {
+    pub value: T,
+    drops: &'static AtomicUsize,
+}
+
+impl<T> DetectDrop<T> {
+    pub const fn new(value: T, drops: &'static AtomicUsize) -> Self {
+        Self { value, drops }
+    }
+}
+
+impl<T> Drop for DetectDrop<T> {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Relaxed);
+    }
+}
+
+// clone 도 drop 될 때 같은 counter 를 올림
+impl<T: Clone> Clone for DetectDrop<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone(), self.drops)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for DetectDrop<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
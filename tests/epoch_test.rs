@@ -0,0 +1,153 @@
+mod common;
+
+use atomic_and_locks::reclaim::epoch::{Atomic, Collector, Guard, Owned, Pointer, Shared};
+use common::DetectDrop;
+use std::collections::HashSet;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release, SeqCst};
+use std::thread;
+
+static NUM_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+// Treiber stack: pop 된 node 를 다른 스레드가 아직 읽고 있을 수 있음
+struct Stack {
+    head: Atomic<Node>,
+}
+
+struct Node {
+    value: usize,
+    next: *const Node,
+    _drop: DetectDrop,
+}
+
+unsafe impl Sync for Stack {}
+
+impl Stack {
+    fn push(&self, value: usize, guard: &Guard) {
+        let mut node = Owned::new(Node {
+            value,
+            next: std::ptr::null(),
+            _drop: DetectDrop::new((), &NUM_DROPS),
+        });
+        loop {
+            let head = self.head.load(Relaxed, guard);
+            node.next = head.as_raw();
+            match self
+                .head
+                .compare_exchange(head, node, Release, Relaxed, guard)
+            {
+                Ok(_) => return,
+                Err(e) => node = e.new,
+            }
+        }
+    }
+
+    fn pop(&self, guard: &Guard) -> Option<usize> {
+        loop {
+            let head = self.head.load(Acquire, guard);
+            // 안전함: pin 된 동안 pop 된 node 도 해제되지 않음
+            let node = unsafe { head.as_ref() }?;
+            let next: Shared<'_, Node> = unsafe { Shared::from_ptr(node.next as *mut Node) };
+            if self
+                .head
+                .compare_exchange(head, next, Acquire, Relaxed, guard)
+                .is_ok()
+            {
+                let value = node.value;
+                unsafe { guard.defer_destroy(head) };
+                return Some(value);
+            }
+        }
+    }
+}
+
+#[test]
+fn stress() {
+    const THREADS: usize = 8;
+    const OPS: usize = 10_000;
+
+    let collector = Collector::new();
+    let stack = Stack {
+        head: Atomic::null(),
+    };
+    let popped: Vec<usize> = thread::scope(|s| {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let (collector, stack) = (&collector, &stack);
+                s.spawn(move || {
+                    let handle = collector.register();
+                    let mut popped = Vec::new();
+                    for i in 0..OPS {
+                        let guard = handle.pin();
+                        stack.push(t * OPS + i, &guard);
+                        if i % 2 == 1 {
+                            // 각 스레드가 넣은 만큼만 빼므로 비어 있을 수 없음
+                            popped.push(stack.pop(&guard).unwrap());
+                            popped.push(stack.pop(&guard).unwrap());
+                        }
+                    }
+                    popped
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect()
+    });
+
+    // 같은 node 를 두 번 pop 하지 않음
+    assert_eq!(popped.iter().collect::<HashSet<_>>().len(), THREADS * OPS);
+    assert!(stack
+        .head
+        .load(SeqCst, &collector.register().pin())
+        .is_null());
+
+    // 아직 대기 중인 node 는 Collector 와 handle 이 모두 사라질 때 해제
+    assert!(NUM_DROPS.load(Relaxed) <= THREADS * OPS);
+    drop(collector);
+    assert_eq!(NUM_DROPS.load(Relaxed), THREADS * OPS);
+}
+
+#[test]
+fn pinned_guard_delays_destroy() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    let data = || DetectDrop::new((), &DROPS);
+
+    let collector = Collector::new();
+    let reader = collector.register();
+    let writer = collector.register();
+    let a = Atomic::new(data());
+
+    let guard = reader.pin();
+    let seen = a.load(SeqCst, &guard);
+    {
+        let guard = writer.pin();
+        let old = a.swap(Owned::new(data()), SeqCst, &guard);
+        assert_eq!(old, seen);
+        unsafe { guard.defer_destroy(old) };
+    }
+    // reader 가 pin 된 동안에는 epoch 가 두 번 넘어갈 수 없음
+    for _ in 0..10 {
+        writer.pin().flush();
+    }
+    assert_eq!(DROPS.load(Relaxed), 0);
+    let _still_valid: &DetectDrop = unsafe { seen.deref() };
+
+    drop(guard);
+    for _ in 0..10 {
+        writer.pin().flush();
+    }
+    assert_eq!(DROPS.load(Relaxed), 1);
+
+    // defer 는 임의의 함수도 받음
+    let guard = writer.pin();
+    guard.defer(|| {
+        DROPS.fetch_add(10, Relaxed);
+    });
+    drop(guard);
+    drop((reader, writer, collector));
+    assert_eq!(DROPS.load(Relaxed), 11);
+    drop(unsafe { a.into_owned() });
+    assert_eq!(DROPS.load(Relaxed), 12);
+}
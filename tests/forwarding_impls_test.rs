@@ -0,0 +1,47 @@
+use atomic_and_locks::arc::{ArcMake, WeakMake};
+use std::collections::{BTreeSet, HashMap};
+
+#[test]
+fn forwarding_impls() {
+    let a = ArcMake::new(String::from("a"));
+    assert_eq!(format!("{a:?} {a}"), "\"a\" a");
+    assert_eq!(format!("{a:p}"), format!("{:p}", ArcMake::as_ptr(&a)));
+    assert_eq!(format!("{:?}", ArcMake::downgrade(&a)), "(Weak)");
+    assert_eq!(format!("{:?}", WeakMake::<i32>::new()), "(Weak)");
+
+    // 포인터가 아니라 data 로 비교
+    let b = ArcMake::from(String::from("b"));
+    assert_eq!(a, ArcMake::new(String::from("a")));
+    assert!(a < b && b.cmp(&a).is_gt());
+    let set: BTreeSet<_> = [b.clone(), a.clone(), b.clone()].into();
+    assert_eq!(set.len(), 2);
+
+    // Borrow<str> 가 아니라 Borrow<String>: &String 으로 찾음
+    let mut map = HashMap::new();
+    map.insert(a.clone(), 1);
+    assert_eq!(map.get(&String::from("a")), Some(&1));
+    let s: &String = a.as_ref();
+    assert_eq!(s, "a");
+
+    let d: ArcMake<Vec<i32>> = ArcMake::default();
+    assert!(d.is_empty());
+    assert_eq!(ArcMake::unwrap_or_clone(ArcMake::from(5)), 5);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    use serde_test::{assert_tokens, Token};
+
+    assert_tokens(&ArcMake::new(5u32), &[Token::U32(5)]);
+    assert_tokens(&ArcMake::<str>::from("hi"), &[Token::Str("hi")]);
+    assert_tokens(
+        &ArcMake::<[u8]>::from(vec![1, 2]),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::U8(1),
+            Token::U8(2),
+            Token::SeqEnd,
+        ],
+    );
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use atomic_and_locks::reclaim::hazard::{HazardDomain, HazardPointer, DEFAULT_THRESHOLD};
+use common::DetectDrop;
+use std::ptr;
+use std::sync::atomic::Ordering::{Relaxed, SeqCst};
+use std::sync::atomic::{AtomicPtr, AtomicUsize};
+use std::thread;
+
+struct Node {
+    value: usize,
+    next: *mut Node,
+    _drop: DetectDrop,
+}
+
+unsafe fn delete(node: *mut Node) {
+    drop(Box::from_raw(node));
+}
+
+fn new_node(value: usize, drops: &'static AtomicUsize) -> *mut Node {
+    Box::into_raw(Box::new(Node {
+        value,
+        next: ptr::null_mut(),
+        _drop: DetectDrop::new((), drops),
+    }))
+}
+
+// Treiber stack: pop 이 head 를 보호한 뒤 next 를 읽음
+struct Stack<'d> {
+    head: AtomicPtr<Node>,
+    domain: &'d HazardDomain,
+}
+
+impl Stack<'_> {
+    fn push(&self, node: *mut Node) {
+        let mut head = self.head.load(Relaxed);
+        loop {
+            unsafe { (*node).next = head };
+            match self.head.compare_exchange(head, node, SeqCst, Relaxed) {
+                Ok(_) => return,
+                Err(h) => head = h,
+            }
+        }
+    }
+
+    fn pop(&self, hp: &mut HazardPointer) -> Option<usize> {
+        loop {
+            // 안전함: head 의 node 는 pop 에서 retire 로만 해제됨
+            let head = unsafe { hp.protect(&self.head) }? as *const Node as *mut Node;
+            // 안전함: head 는 보호 중이라 retire 돼도 삭제되지 않음
+            let next = unsafe { (*head).next };
+            if self
+                .head
+                .compare_exchange(head, next, SeqCst, Relaxed)
+                .is_ok()
+            {
+                let value = unsafe { (*head).value };
+                hp.reset_protection();
+                unsafe { self.domain.retire(head, delete) };
+                return Some(value);
+            }
+        }
+    }
+}
+
+#[test]
+fn use_after_free() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    let domain = HazardDomain::new();
+    let src = AtomicPtr::new(new_node(1, &DROPS));
+
+    let mut hp = HazardPointer::new_in(&domain);
+    let node = unsafe { hp.protect(&src) }.unwrap();
+
+    // 다른 스레드가 교체하고 retire + reclaim
+    thread::scope(|s| {
+        s.spawn(|| {
+            let old = src.swap(new_node(2, &DROPS), SeqCst);
+            unsafe { domain.retire(old, delete) };
+            assert_eq!(domain.reclaim(), 0);
+        });
+    });
+    // 보호 중이라 아직 읽을 수 있음
+    assert_eq!(node.value, 1);
+    assert_eq!((DROPS.load(Relaxed), domain.pending()), (0, 1));
+
+    hp.reset_protection();
+    assert_eq!(domain.reclaim(), 1);
+    assert_eq!(DROPS.load(Relaxed), 1);
+
+    // 다시 protect 하면 새 값
+    assert_eq!(unsafe { hp.protect(&src) }.unwrap().value, 2);
+    drop(hp);
+    unsafe { delete(src.into_inner()) };
+    assert_eq!(DROPS.load(Relaxed), 2);
+}
+
+#[test]
+fn aba() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    let domain = HazardDomain::new();
+    let stack = Stack {
+        head: AtomicPtr::new(ptr::null_mut()),
+        domain: &domain,
+    };
+    stack.push(new_node(2, &DROPS));
+    stack.push(new_node(1, &DROPS));
+
+    // 느린 pop: head (1) 를 보호하고 next (2) 를 읽은 채 멈춤
+    let mut slow = HazardPointer::new_in(&domain);
+    let a = unsafe { slow.protect(&stack.head) }.unwrap() as *const Node as *mut Node;
+    let stale_next = unsafe { (*a).next };
+
+    // 그 사이 다른 pop 두 번, reclaim, push
+    let mut hp = HazardPointer::new_in(&domain);
+    assert_eq!(stack.pop(&mut hp), Some(1));
+    assert_eq!(stack.pop(&mut hp), Some(2));
+    assert_eq!(domain.reclaim(), 1);
+    // a 는 보호 중이라 삭제되지 않았으니 새 node 가 같은 주소를 받을 수 없음
+    assert_eq!(DROPS.load(Relaxed), 1);
+    let c = new_node(3, &DROPS);
+    assert_ne!(c, a);
+    stack.push(c);
+
+    // 주소가 재사용되지 않았으므로 오래된 next 로의 CAS 는 실패
+    assert!(stack
+        .head
+        .compare_exchange(a, stale_next, SeqCst, Relaxed)
+        .is_err());
+
+    drop(slow);
+    assert_eq!(domain.reclaim(), 1);
+    assert_eq!(stack.pop(&mut hp), Some(3));
+    drop(hp);
+    drop(domain);
+    assert_eq!(DROPS.load(Relaxed), 3);
+}
+
+#[test]
+fn stress() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    const THREADS: usize = 8;
+    const OPS: usize = 10_000;
+
+    let domain = HazardDomain::new();
+    let stack = Stack {
+        head: AtomicPtr::new(ptr::null_mut()),
+        domain: &domain,
+    };
+    let max_pending = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for t in 0..THREADS {
+            let (stack, max_pending) = (&stack, &max_pending);
+            s.spawn(move || {
+                let mut hp = HazardPointer::new_in(stack.domain);
+                for i in 0..OPS {
+                    stack.push(new_node(t * OPS + i, &DROPS));
+                    assert!(stack.pop(&mut hp).is_some());
+                    max_pending.fetch_max(stack.domain.pending(), Relaxed);
+                }
+            });
+        }
+    });
+    assert!(stack.head.load(SeqCst).is_null());
+
+    // 대기 중인 retired 객체 수는 threshold 근처로 제한됨
+    let bound = DEFAULT_THRESHOLD.max(2 * THREADS) + 2 * THREADS;
+    assert!(max_pending.load(Relaxed) <= bound);
+
+    domain.reclaim();
+    assert_eq!(domain.pending(), 0);
+    assert_eq!(DROPS.load(Relaxed), THREADS * OPS);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use atomic_and_locks::arc::{ArcMake, StaticArcMake};
+use atomic_and_locks::memory_ordering::LazyBox;
+use common::DetectDrop;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+
+#[test]
+fn immortal() {
+    static NUM_DROPS: AtomicUsize = AtomicUsize::new(0);
+    static TABLE: StaticArcMake<DetectDrop<u32>> =
+        StaticArcMake::new(DetectDrop::new(1, &NUM_DROPS));
+
+    let a = ArcMake::from_static(&TABLE);
+    let b = ArcMake::from_static(&TABLE);
+    assert!(ArcMake::ptr_eq(&a, &b));
+    let strong = ArcMake::strong_count(&a);
+    assert!(strong > atomic_and_locks::arc::arc_make::MAX_REFCOUNT);
+
+    // clone/drop/upgrade 가 counter 를 바꾸지 않음
+    let w = ArcMake::downgrade(&a);
+    let c = w.upgrade().unwrap();
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..1000 {
+                    drop(a.clone());
+                }
+            });
+        }
+    });
+    drop((b, c, w));
+    assert_eq!(ArcMake::strong_count(&a), strong);
+    assert_eq!(ArcMake::weak_count(&a), 0);
+
+    let a = ArcMake::try_unwrap(a).err().unwrap();
+    assert!(ArcMake::into_inner(a.clone()).is_none());
+    assert_eq!(ArcMake::try_clone(&a).map(|a| a.value).ok(), Some(1));
+
+    // make_mut 은 일반 allocation 으로 복사
+    let mut a = a;
+    ArcMake::make_mut(&mut a).value = 2;
+    assert_eq!(ArcMake::strong_count(&a), 1);
+    assert_eq!((a.value, ArcMake::from_static(&TABLE).value), (2, 1));
+    drop(a);
+    assert_eq!(NUM_DROPS.load(Relaxed), 1);
+
+    // get_data() 식 lazy init 한 global
+    static LAZY: LazyBox<ArcMake<DetectDrop<u32>>> = LazyBox::new();
+    let get = || {
+        LAZY.get_or_init(|| ArcMake::new_immortal(DetectDrop::new(3, &NUM_DROPS)))
+            .clone()
+    };
+    let x = get();
+    assert_eq!(get().value, 3);
+    assert!(ArcMake::ptr_eq(&x, &get()));
+    drop(x);
+    assert_eq!(NUM_DROPS.load(Relaxed), 1);
+}
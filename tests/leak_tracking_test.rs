@@ -0,0 +1,59 @@
+#![cfg(feature = "leak-tracking")]
+
+use atomic_and_locks::arc::arc_make::live_allocations;
+use atomic_and_locks::arc::{ArcMake, WeakMake};
+use std::sync::Mutex;
+
+#[test]
+fn leak_tracking() {
+    struct LeakNode {
+        next: Mutex<Option<ArcMake<LeakNode>>>,
+        prev: Mutex<WeakMake<LeakNode>>,
+    }
+
+    let new = || {
+        ArcMake::new(LeakNode {
+            next: Mutex::new(None),
+            prev: Mutex::new(WeakMake::new()),
+        })
+    };
+    let nodes = || {
+        live_allocations()
+            .into_iter()
+            // 전체 type 이름으로 비교: 같은 binary 의 다른 테스트 allocation 은 제외
+            .filter(|a| a.type_name == std::any::type_name::<LeakNode>())
+            .collect::<Vec<_>>()
+    };
+
+    // 되돌아가는 포인터가 WeakMake: 누수 없음
+    let a = new();
+    let b = new();
+    *a.next.lock().unwrap() = Some(b.clone());
+    *b.prev.lock().unwrap() = ArcMake::downgrade(&a);
+    let counts: Vec<_> = nodes()
+        .iter()
+        .map(|a| (a.strong_count, a.weak_count))
+        .collect();
+    assert_eq!(counts, [(1, 1), (2, 0)]);
+    drop((a, b));
+    assert!(nodes().is_empty());
+
+    // ArcMake 순환: 둘 다 남음
+    let a = new();
+    let b = new();
+    *a.next.lock().unwrap() = Some(b.clone());
+    *b.next.lock().unwrap() = Some(a.clone());
+    let w = ArcMake::downgrade(&a);
+    drop((a, b));
+    let leaked = nodes();
+    assert_eq!(leaked.len(), 2);
+    assert!(leaked.iter().all(|a| a.strong_count == 1));
+    assert!(leaked[0].backtrace.to_string().contains("leak_tracking"));
+
+    // 순환을 끊으면 정리됨
+    let next = w.upgrade().unwrap().next.lock().unwrap().take();
+    drop(next);
+    assert!(w.upgrade().is_none());
+    drop(w);
+    assert!(nodes().is_empty());
+}
@@ -0,0 +1,96 @@
+use atomic_and_locks::channel::oneshot::{
+    self, atomic_state, borrowed, checked, Canceled, TryRecvError,
+};
+use std::thread;
+
+#[test]
+fn checked_try_receive() {
+    let channel = checked::Channel::new();
+    assert_eq!(channel.try_receive(), Err(TryRecvError::Empty));
+    assert_eq!(channel.try_send(String::from("a")), Ok(()));
+    // 두 번째 message 는 panic 대신 돌려받음
+    assert_eq!(channel.try_send(String::from("b")), Err(String::from("b")));
+    assert_eq!(channel.try_receive().as_deref(), Ok("a"));
+    assert_eq!(channel.try_receive(), Err(TryRecvError::AlreadyTaken));
+}
+
+#[test]
+fn atomic_state_try_receive() {
+    let channel = atomic_state::Channel::new();
+    assert_eq!(channel.try_receive(), Err(TryRecvError::Empty));
+    assert_eq!(channel.try_send(1), Ok(()));
+    assert_eq!(channel.try_send(2), Err(2));
+    assert_eq!(channel.try_receive(), Ok(1));
+    let err = channel.try_receive().unwrap_err();
+    assert_eq!(err, TryRecvError::AlreadyTaken);
+    assert_eq!(err.to_string(), "message already received");
+}
+
+#[test]
+fn typed_try_receive() {
+    let (sender, receiver) = oneshot::channel();
+    assert_eq!(receiver.try_receive(), Err(TryRecvError::Empty));
+    thread::spawn(move || sender.send(1)).join().unwrap();
+    assert_eq!(receiver.try_receive(), Ok(1));
+    assert_eq!(receiver.try_receive(), Err(TryRecvError::AlreadyTaken));
+
+    let (sender, receiver) = oneshot::channel::<i32>();
+    drop(sender);
+    assert_eq!(receiver.try_receive(), Err(TryRecvError::SenderDropped));
+}
+
+#[test]
+fn borrowed_try_receive() {
+    let mut channel = borrowed::Channel::new();
+    thread::scope(|s| {
+        let (sender, receiver) = channel.split();
+        assert_eq!(receiver.try_receive(), Err(TryRecvError::Empty));
+        s.spawn(move || sender.send("hi")).join().unwrap();
+        assert_eq!(receiver.try_receive(), Ok("hi"));
+        assert_eq!(receiver.try_receive(), Err(TryRecvError::AlreadyTaken));
+    });
+
+    // split 하면 상태가 초기화됨
+    let (sender, receiver) = channel.split();
+    drop(sender);
+    assert_eq!(receiver.try_receive(), Err(TryRecvError::SenderDropped));
+}
+
+#[test]
+fn typed_canceled() {
+    let (sender, receiver) = oneshot::channel::<i32>();
+    let t = thread::current();
+    thread::spawn(move || {
+        // 보내지 않고 drop
+        drop(sender);
+        t.unpark();
+    });
+    while !receiver.is_ready() {
+        thread::park();
+    }
+    let err = receiver.receive().unwrap_err();
+    assert_eq!(err, Canceled);
+    assert_eq!(err.to_string(), "sender dropped without sending");
+
+    // send 뒤의 drop 은 취소가 아님
+    let (sender, receiver) = oneshot::channel();
+    sender.send(1);
+    assert_eq!(receiver.receive(), Ok(1));
+}
+
+#[test]
+fn checked_concurrent_try_receive() {
+    // 가져간 뒤에는 어느 스레드도 Empty 를 보지 않음
+    for _ in 0..100 {
+        let channel = checked::Channel::new();
+        channel.send(1);
+        let results: Vec<_> = thread::scope(|s| {
+            let handles: Vec<_> = (0..4).map(|_| s.spawn(|| channel.try_receive())).collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        assert!(results
+            .iter()
+            .all(|r| matches!(r, Ok(1) | Err(TryRecvError::AlreadyTaken))));
+    }
+}
@@ -0,0 +1,142 @@
+use atomic_and_locks::channel::mpsc::{
+    bounded, channel, Channel, RecvError, RecvTimeoutError, SendError, TrySendError,
+};
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn bounded_backpressure() {
+    let capacity = 2;
+    let channel = Channel::bounded(capacity);
+    assert_eq!(channel.capacity(), Some(capacity));
+    channel.send(0);
+    channel.send(1);
+    let prefilled = 2;
+    // 받기 전에는 capacity 만큼만 보낼 수 있음
+    assert_eq!(channel.try_send(9), Err(TrySendError::Full(9)));
+
+    let sent = AtomicUsize::new(0);
+    thread::scope(|s| {
+        // 가득 찬 channel 에 보내므로 receive 가 자리를 내야 진행됨
+        s.spawn(|| {
+            for i in 2..5 {
+                channel.send(i);
+                sent.fetch_add(1, Relaxed);
+            }
+        });
+        // sender 가 먼저 진행할 기회: 막히지 않는 send 라면 여기서 다 보냄
+        for _ in 0..100 {
+            thread::yield_now();
+        }
+        let received: Vec<i32> = (1..=5)
+            .map(|n| {
+                let message = channel.receive();
+                // send 가 막히지 않았다면 아직 받지 않은 만큼 더 보냈을 수 있음
+                assert!(sent.load(Relaxed) <= n + capacity - prefilled);
+                message
+            })
+            .collect();
+        assert_eq!(received, [0, 1, 2, 3, 4]);
+    });
+    assert_eq!(sent.load(Relaxed), 3);
+}
+
+#[test]
+fn try_send_full() {
+    let channel = Channel::bounded(1);
+    assert_eq!(channel.try_send(String::from("a")), Ok(()));
+    let err = channel.try_send(String::from("b")).unwrap_err();
+    assert_eq!(err.to_string(), "sending on a full channel");
+    assert_eq!(err, TrySendError::Full(String::from("b")));
+    assert_eq!(err.into_inner(), "b");
+
+    assert_eq!(channel.receive(), "a");
+    assert_eq!(channel.try_send(String::from("c")), Ok(()));
+
+    // 제한 없는 channel 은 가득 차지 않음
+    let unbounded = Channel::new();
+    assert_eq!(unbounded.capacity(), None);
+    for i in 0..1000 {
+        unbounded.try_send(i).unwrap();
+    }
+}
+
+#[test]
+#[should_panic(expected = "capacity must be at least 1")]
+fn bounded_zero() {
+    Channel::<()>::bounded(0);
+}
+
+#[test]
+fn senders_dropped() {
+    let (tx, rx) = channel();
+    thread::scope(|s| {
+        for t in 0..3 {
+            let tx = tx.clone();
+            s.spawn(move || {
+                for i in 0..10 {
+                    tx.send(t * 10 + i).unwrap();
+                }
+            });
+        }
+        drop(tx);
+        // 모든 sender 가 사라지면 남은 message 를 받은 뒤 끝남
+        let mut received: Vec<i32> = std::iter::from_fn(|| rx.recv().ok()).collect();
+        received.sort();
+        assert_eq!(received, (0..30).collect::<Vec<_>>());
+    });
+    assert_eq!(rx.recv(), Err(RecvError));
+}
+
+#[test]
+fn receivers_dropped() {
+    let (tx, rx) = bounded(1);
+    let rx2 = rx.clone();
+    tx.send(1).unwrap();
+    drop(rx);
+    // receiver 가 하나라도 남아 있으면 끊기지 않음
+    assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+    thread::scope(|s| {
+        let blocked = s.spawn(|| tx.send(3));
+        thread::sleep(Duration::from_millis(100));
+        // 가득 차서 기다리던 send 도 깨어나서 message 를 돌려받음
+        drop(rx2);
+        let err = blocked.join().unwrap().unwrap_err();
+        assert_eq!(err, SendError(3));
+        assert_eq!(err.to_string(), "sending on a closed channel");
+    });
+    assert_eq!(tx.try_send(4), Err(TrySendError::Disconnected(4)));
+}
+
+#[test]
+fn recv_timeout() {
+    let (tx, rx) = channel();
+    let start = Instant::now();
+    assert_eq!(
+        rx.recv_timeout(Duration::from_millis(50)),
+        Err(RecvTimeoutError::Timeout)
+    );
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            thread::sleep(Duration::from_millis(50));
+            tx.send(1).unwrap();
+        });
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok(1));
+    });
+    // 너무 긴 timeout 도 overflow 없이 기다림
+    tx.send(2).unwrap();
+    assert_eq!(rx.recv_timeout(Duration::MAX), Ok(2));
+
+    // sender 가 없으면 deadline 전이라도 바로 끝남
+    drop(tx);
+    let deadline = Instant::now() + Duration::from_secs(10);
+    assert_eq!(
+        rx.recv_deadline(deadline),
+        Err(RecvTimeoutError::Disconnected)
+    );
+    assert!(Instant::now() < deadline);
+}
@@ -0,0 +1,91 @@
+use atomic_and_locks::channel::oneshot::blocking::{Channel, RecvTimeoutError};
+use atomic_and_locks::channel::oneshot::Canceled;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn recv_timeout() {
+    let mut channel = Channel::new();
+    thread::scope(|s| {
+        let (sender, receiver) = channel.split();
+
+        let start = Instant::now();
+        let err = receiver
+            .recv_timeout(Duration::from_millis(50))
+            .unwrap_err();
+        assert_eq!(err, RecvTimeoutError::Timeout);
+        assert_eq!(err.to_string(), "timed out waiting on channel");
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        // timeout 뒤에도 다시 기다릴 수 있음
+        s.spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            sender.send("hi");
+        });
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(10)), Ok("hi"));
+    });
+}
+
+#[test]
+fn recv_deadline_spurious_unpark() {
+    let mut channel = Channel::<i32>::new();
+    thread::scope(|s| {
+        let (_sender, receiver) = channel.split();
+        let me = thread::current();
+        s.spawn(move || {
+            // message 없이 깨워도 deadline 까지 기다림
+            for _ in 0..5 {
+                thread::sleep(Duration::from_millis(10));
+                me.unpark();
+            }
+        });
+        let deadline = Instant::now() + Duration::from_millis(150);
+        assert_eq!(
+            receiver.recv_deadline(deadline),
+            Err(RecvTimeoutError::Timeout)
+        );
+        assert!(Instant::now() >= deadline);
+    });
+}
+
+#[test]
+fn sender_dropped_wakes_receiver() {
+    let mut channel = Channel::<i32>::new();
+    thread::scope(|s| {
+        let (sender, receiver) = channel.split();
+        s.spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            drop(sender);
+        });
+        // park 중인 receiver 를 깨워서 Canceled 를 돌려줌
+        assert_eq!(receiver.receive(), Err(Canceled));
+    });
+
+    let (sender, receiver) = channel.split();
+    drop(sender);
+    assert_eq!(
+        receiver.recv_timeout(Duration::from_secs(10)),
+        Err(RecvTimeoutError::Canceled)
+    );
+}
+
+#[test]
+#[should_panic(expected = "no message available!")]
+fn receive_after_recv_timeout() {
+    let mut channel = Channel::new();
+    let (sender, receiver) = channel.split();
+    sender.send(5);
+    assert_eq!(receiver.recv_timeout(Duration::from_secs(10)), Ok(5));
+    // 이미 받았으므로 park 하지 않고 panic
+    let _ = receiver.receive();
+}
+
+#[test]
+#[should_panic(expected = "no message available!")]
+fn recv_timeout_twice() {
+    let mut channel = Channel::new();
+    let (sender, receiver) = channel.split();
+    sender.send(5);
+    assert_eq!(receiver.recv_timeout(Duration::from_secs(10)), Ok(5));
+    let _ = receiver.recv_timeout(Duration::from_secs(10));
+}
@@ -0,0 +1,329 @@
+mod common;
+
+use atomic_and_locks::arc::ArcMake;
+use common::DetectDrop;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+
+#[test]
+fn test() {
+    static NUM_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    // 2개 weak pointer 에서 Arc 생성
+    let x = ArcMake::new(("hi", DetectDrop::new((), &NUM_DROPS)));
+    let y = ArcMake::downgrade(&x);
+    let z = ArcMake::downgrade(&x);
+
+    let t = std::thread::spawn(move || {
+        // upgrade weak pointer
+        let y = y.upgrade().unwrap();
+        assert_eq!(y.0, "hi");
+    });
+    assert_eq!(x.0, "hi");
+    t.join().unwrap();
+
+    // data 는 아직 메모리에서 삭제 안됨
+    // weak pointer upgrade 가능
+    assert_eq!(NUM_DROPS.load(Relaxed), 0);
+    assert!(z.upgrade().is_some());
+
+    drop(x);
+
+    // deleted data
+    // weak pointer upgrade 불가능
+    assert_eq!(NUM_DROPS.load(Relaxed), 1);
+    assert!(z.upgrade().is_none());
+}
+
+#[test]
+fn make_mut() {
+    // 유일한 참조: 복제 없음
+    let mut a = ArcMake::new(String::from("a"));
+    let before: *const String = &*a;
+    ArcMake::make_mut(&mut a).push('1');
+    assert_eq!(before, &*a as *const String);
+    assert_eq!(*a, "a1");
+
+    // 다른 ArcMake 존재: 복제
+    let b = a.clone();
+    ArcMake::make_mut(&mut a).push('2');
+    assert_eq!(*a, "a12");
+    assert_eq!(*b, "a1");
+
+    // WeakMake 만 남음: data 이동, WeakMake 분리
+    let w = ArcMake::downgrade(&a);
+    drop(b);
+    ArcMake::make_mut(&mut a).push('3');
+    assert_eq!(*a, "a123");
+    assert!(w.upgrade().is_none());
+    assert!(ArcMake::get_mut(&mut a).is_some());
+}
+
+#[test]
+fn unsized_data() {
+    use atomic_and_locks::unsize_arc_make;
+    use std::fmt::Display;
+
+    let s: ArcMake<str> = ArcMake::from("interned");
+    let w = ArcMake::downgrade(&s);
+    assert_eq!(&*w.upgrade().unwrap(), "interned");
+    drop(s);
+    assert!(w.upgrade().is_none());
+
+    let v: ArcMake<[String]> = ArcMake::from(vec![String::from("a"), String::from("b")]);
+    assert_eq!(v.len(), 2);
+    let c: ArcMake<[u32]> = (1..=4).collect();
+    assert_eq!(c.iter().sum::<u32>(), 10);
+    let e: ArcMake<[u8]> = ArcMake::from(&[][..]);
+    assert!(e.is_empty());
+
+    static NUM_DROPS: AtomicUsize = AtomicUsize::new(0);
+    let d = unsize_arc_make!(ArcMake::new(DetectDrop::new(7u64, &NUM_DROPS)) => dyn Display + Send + Sync);
+    let w = ArcMake::downgrade(&d);
+    let t = std::thread::spawn(move || w.upgrade().unwrap().to_string());
+    assert_eq!(t.join().unwrap(), "7");
+    // data 가 drop 된 뒤에도 WeakMake 가 allocation 을 해제할 수 있음
+    let w = ArcMake::downgrade(&d);
+    drop(d);
+    assert_eq!(NUM_DROPS.load(Relaxed), 1);
+    assert!(w.upgrade().is_none());
+    assert_eq!(w.strong_count(), 0);
+    drop(w);
+
+    let l = unsize_arc_make!(ArcMake::new([String::from("a"), String::from("b")]) => dyn std::fmt::Debug);
+    let w = ArcMake::downgrade(&l);
+    drop(l);
+    assert!(w.upgrade().is_none());
+    assert_eq!((w.strong_count(), w.weak_count()), (0, 1));
+}
+
+#[test]
+fn raw_pointers() {
+    use atomic_and_locks::arc::WeakMake;
+    use std::sync::atomic::AtomicPtr;
+    use std::sync::atomic::Ordering::{Acquire, Release};
+
+    let a = ArcMake::new(42u64);
+    assert_eq!(ArcMake::as_ptr(&a), &*a as *const u64);
+
+    // AtomicPtr slot 을 통해 전달
+    let slot = AtomicPtr::new(ArcMake::into_raw(a.clone()) as *mut u64);
+    let p = slot.load(Acquire) as usize;
+    unsafe { ArcMake::increment_strong_count(p as *const u64) };
+    let t = std::thread::spawn(move || *unsafe { ArcMake::from_raw(p as *const u64) });
+    assert_eq!(t.join().unwrap(), 42);
+    unsafe { ArcMake::decrement_strong_count(slot.swap(std::ptr::null_mut(), Release)) };
+    assert!(ArcMake::get_mut(&mut a.clone()).is_none());
+
+    let s: ArcMake<str> = ArcMake::from("raw");
+    let s = unsafe { ArcMake::from_raw(ArcMake::into_raw(s)) };
+    assert_eq!(&*s, "raw");
+
+    // data 가 drop 된 뒤에도 WeakMake 로 되돌릴 수 있음
+    let s = ArcMake::new(String::from("raw"));
+    let w = ArcMake::downgrade(&s);
+    let p = w.into_raw();
+    unsafe { WeakMake::increment_weak_count(p) };
+    unsafe { WeakMake::decrement_weak_count(p) };
+    drop(s);
+    let w = unsafe { WeakMake::from_raw(p) };
+    assert!(w.upgrade().is_none());
+
+    // slice 와 str 도 pointer 만으로 align 을 알 수 있음
+    let v: ArcMake<[u16]> = ArcMake::from(vec![1, 2, 3]);
+    let p = ArcMake::downgrade(&v).into_raw();
+    drop(v);
+    let w = unsafe { WeakMake::from_raw(p) };
+    assert!(w.upgrade().is_none());
+    let s: ArcMake<str> = ArcMake::from("weak");
+    let w = unsafe { WeakMake::from_raw(ArcMake::downgrade(&s).into_raw()) };
+    assert_eq!(&*w.upgrade().unwrap(), "weak");
+
+    let mut a = a;
+    assert!(ArcMake::get_mut(&mut a).is_some());
+}
+
+#[test]
+fn unwrap() {
+    use atomic_and_locks::arc::basic;
+
+    let a = ArcMake::new(String::from("x"));
+    let b = a.clone();
+    let w = ArcMake::downgrade(&a);
+    let a = ArcMake::try_unwrap(a).unwrap_err();
+    assert_eq!(ArcMake::unwrap_or_clone(b), "x");
+    assert_eq!(ArcMake::try_unwrap(a).ok().as_deref(), Some("x"));
+    assert!(w.upgrade().is_none());
+
+    // 동시에 into_inner 호출: 정확히 하나만 Some
+    for _ in 0..100 {
+        let a = ArcMake::new(vec![1, 2, 3]);
+        let b = basic::ArcMake::new(vec![1, 2, 3]);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let a = a.clone();
+                let b = b.clone();
+                std::thread::spawn(move || {
+                    (
+                        ArcMake::into_inner(a).is_some() as usize,
+                        basic::ArcMake::into_inner(b).is_some() as usize,
+                    )
+                })
+            })
+            .collect();
+        let (mut x, mut y) = (
+            ArcMake::into_inner(a).is_some() as usize,
+            basic::ArcMake::into_inner(b).is_some() as usize,
+        );
+        for h in handles {
+            let (a, b) = h.join().unwrap();
+            x += a;
+            y += b;
+        }
+        assert_eq!((x, y), (1, 1));
+    }
+
+    let b = basic::ArcMake::new(5);
+    let c = b.clone();
+    let b = basic::ArcMake::try_unwrap(b).unwrap_err();
+    assert_eq!(basic::ArcMake::unwrap_or_clone(c), 5);
+    assert_eq!(basic::ArcMake::try_unwrap(b).ok(), Some(5));
+}
+
+#[test]
+fn new_cyclic() {
+    use atomic_and_locks::arc::WeakMake;
+
+    struct Node {
+        this: WeakMake<Node>,
+        value: i32,
+    }
+
+    let node = ArcMake::new_cyclic(|this| {
+        // 생성 중에는 upgrade 불가능
+        assert!(this.upgrade().is_none());
+        Node {
+            this: this.clone(),
+            value: 7,
+        }
+    });
+    let again = node.this.upgrade().unwrap();
+    assert_eq!(again.value, 7);
+    assert!(std::ptr::eq(&*again, &*node));
+    drop(again);
+
+    let this = node.this.clone();
+    drop(node);
+    assert!(this.upgrade().is_none());
+
+    // f 가 panic 해도 allocation 해제
+    let r = std::panic::catch_unwind(|| ArcMake::<i32>::new_cyclic(|_| panic!("boom")));
+    assert!(r.is_err());
+}
+
+#[test]
+fn dangling_weak() {
+    use atomic_and_locks::arc::WeakMake;
+
+    struct Parent {
+        child: WeakMake<String>,
+    }
+
+    let mut p = Parent {
+        child: WeakMake::new(),
+    };
+    assert!(p.child.upgrade().is_none());
+    let c = p.child.clone();
+    assert!(c.upgrade().is_none());
+    drop(c);
+
+    let raw = WeakMake::<String>::new().into_raw();
+    unsafe { WeakMake::increment_weak_count(raw) };
+    let w = unsafe { WeakMake::from_raw(raw) };
+    assert!(w.upgrade().is_none());
+    unsafe { WeakMake::decrement_weak_count(raw) };
+
+    let s = ArcMake::new(String::from("child"));
+    p.child = ArcMake::downgrade(&s);
+    assert_eq!(*p.child.upgrade().unwrap(), "child");
+}
+
+#[test]
+fn counts() {
+    use atomic_and_locks::arc::WeakMake;
+
+    let a = ArcMake::new(1);
+    assert_eq!((ArcMake::strong_count(&a), ArcMake::weak_count(&a)), (1, 0));
+
+    let b = a.clone();
+    let w = ArcMake::downgrade(&a);
+    assert_eq!((ArcMake::strong_count(&a), ArcMake::weak_count(&a)), (2, 1));
+    assert_eq!((w.strong_count(), w.weak_count()), (2, 1));
+    assert!(ArcMake::ptr_eq(&a, &b));
+    assert!(!ArcMake::ptr_eq(&a, &ArcMake::new(1)));
+    assert!(w.ptr_eq(&ArcMake::downgrade(&b)));
+
+    drop(a);
+    drop(b);
+    assert_eq!((w.strong_count(), w.weak_count()), (0, 1));
+
+    let d = WeakMake::<i32>::new();
+    assert_eq!((d.strong_count(), d.weak_count()), (0, 0));
+    assert!(d.ptr_eq(&WeakMake::new()));
+    assert!(!d.ptr_eq(&w));
+}
+
+// ArcMake 와 RcMake 가 같이 쓰는 단일 스레드 테스트
+macro_rules! shared_tests {
+    ($name:ident, $ptr:ident) => {
+        mod $name {
+            use crate::common::DetectDrop;
+            use atomic_and_locks::arc::$ptr;
+            use std::sync::atomic::AtomicUsize;
+            use std::sync::atomic::Ordering::Relaxed;
+
+            #[test]
+            fn drop_and_upgrade() {
+                static NUM_DROPS: AtomicUsize = AtomicUsize::new(0);
+                let x = $ptr::new(("hi", DetectDrop::new((), &NUM_DROPS)));
+                let y = $ptr::downgrade(&x);
+                let z = y.clone();
+
+                let y = y.upgrade().unwrap();
+                assert_eq!(y.0, "hi");
+                drop(y);
+                assert_eq!(x.0, "hi");
+
+                assert_eq!(NUM_DROPS.load(Relaxed), 0);
+                assert!(z.upgrade().is_some());
+
+                let x2 = x.clone();
+                drop(x);
+                assert_eq!(NUM_DROPS.load(Relaxed), 0);
+                drop(x2);
+
+                assert_eq!(NUM_DROPS.load(Relaxed), 1);
+                assert!(z.upgrade().is_none());
+            }
+
+            #[test]
+            fn get_mut() {
+                let mut x = $ptr::new(1);
+                *$ptr::get_mut(&mut x).unwrap() += 1;
+                assert_eq!(*x, 2);
+
+                let y = x.clone();
+                assert!($ptr::get_mut(&mut x).is_none());
+                drop(y);
+
+                let w = $ptr::downgrade(&x);
+                assert!($ptr::get_mut(&mut x).is_none());
+                drop(w);
+                assert!($ptr::get_mut(&mut x).is_some());
+            }
+        }
+    };
+}
+
+shared_tests!(arc_make, ArcMake);
+shared_tests!(rc_make, RcMake);
@@ -0,0 +1,50 @@
+mod common;
+
+use atomic_and_locks::arc::ShardedArc;
+use common::DetectDrop;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+
+#[test]
+fn sharded_arc() {
+    static NUM_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    let a = ShardedArc::new(DetectDrop::new(7, &NUM_DROPS));
+    // 스레드마다 clone/drop 하고, 다른 스레드가 만든 handle 을 넘겨받아 drop
+    let moved: Vec<ShardedArc<DetectDrop<i32>>> = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..32)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut kept = Vec::new();
+                    for i in 0..1000 {
+                        let b = a.clone();
+                        assert_eq!(b.value, 7);
+                        if i % 100 == 0 {
+                            kept.push(b);
+                        }
+                    }
+                    kept
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect()
+    });
+    assert_eq!(ShardedArc::strong_count(&a), 1 + 32 * 10);
+
+    let b = a.clone();
+    drop(a);
+    let mut moved = moved;
+    std::thread::scope(|s| {
+        while !moved.is_empty() {
+            let chunk = moved.split_off(moved.len().saturating_sub(40));
+            s.spawn(move || drop(chunk));
+        }
+    });
+    assert_eq!(NUM_DROPS.load(Relaxed), 0);
+    assert_eq!(ShardedArc::strong_count(&b), 1);
+    std::thread::spawn(move || drop(b)).join().unwrap();
+    assert_eq!(NUM_DROPS.load(Relaxed), 1);
+}